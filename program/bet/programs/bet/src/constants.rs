@@ -5,10 +5,89 @@ pub const BET_STATUS_OPEN: u8 = 0;
 pub const BET_STATUS_ACCEPTED: u8 = 1;
 pub const BET_STATUS_CANCELLED: u8 = 2;
 pub const BET_STATUS_RESOLVED: u8 = 3;
+pub const BET_STATUS_EXPIRED: u8 = 4;
+pub const BET_STATUS_VOIDED: u8 = 5;
+pub const BET_STATUS_DISPUTED: u8 = 6;
+pub const BET_STATUS_PENDING_FINALIZATION: u8 = 7;
+pub const BET_STATUS_PENDING_REFEREE: u8 = 8;
+pub const BET_STATUS_SETTLED: u8 = 9;
+
+// Bet outcome values
+pub const BET_OUTCOME_PENDING: u8 = 0;
+pub const BET_OUTCOME_CREATOR_WINS: u8 = 1;
+pub const BET_OUTCOME_ACCEPTOR_WINS: u8 = 2;
+pub const BET_OUTCOME_PUSH: u8 = 3;
+pub const BET_OUTCOME_VOID: u8 = 4;
 
 // Referee type values
 pub const REFEREE_TYPE_HONOR_SYSTEM: u8 = 0;
 pub const REFEREE_TYPE_ORACLE: u8 = 1;
 pub const REFEREE_TYPE_THIRD_PARTY: u8 = 2;
 pub const REFEREE_TYPE_SMART_CONTRACT: u8 = 3;
+pub const REFEREE_TYPE_MUTUAL_CONSENT: u8 = 4;
+pub const REFEREE_TYPE_PANEL: u8 = 5;
+pub const REFEREE_TYPE_PARLAY: u8 = 6;
+
+// Most referees a Panel bet can name
+pub const MAX_PANEL_SIZE: usize = 5;
+
+// Most named outcomes a parimutuel market can have
+pub const MAX_MARKET_OUTCOMES: usize = 8;
+
+// Fewest and most leg bets a parlay can combine
+pub const MIN_PARLAY_LEGS: usize = 2;
+pub const MAX_PARLAY_LEGS: usize = 5;
+
+// Odds formats
+pub const ODDS_FORMAT_FRACTIONAL: u8 = 0;
+pub const ODDS_FORMAT_DECIMAL: u8 = 1;
+pub const ODDS_FORMAT_AMERICAN: u8 = 2;
+
+// Oracle feed layouts
+pub const ORACLE_FEED_MOCK: u8 = 0;
+
+// Oracle comparators
+pub const ORACLE_COMPARATOR_ABOVE: u8 = 0;
+pub const ORACLE_COMPARATOR_BELOW: u8 = 1;
+
+// Seed for the PDA a SmartContract resolver program signs resolve_bet with: [seed, resolver_state]
+pub const RESOLVER_AUTHORITY_SEED: &[u8] = b"bet-resolver";
+
+// Position sides (transfer_position)
+pub const POSITION_SIDE_CREATOR: u8 = 0;
+pub const POSITION_SIDE_ACCEPTOR: u8 = 1;
+
+// Referee fee kinds
+pub const REFEREE_FEE_FLAT: u8 = 0;
+pub const REFEREE_FEE_BPS: u8 = 1;
+
+// Highest referee fee a bet can offer, in basis points of the pot (20%)
+pub const MAX_REFEREE_FEE_BPS: u64 = 2_000;
+
+// Dispute fallback values
+pub const DISPUTE_FALLBACK_REFUND: u8 = 0;
+pub const DISPUTE_FALLBACK_ARBITER: u8 = 1;
+
+// Minimum time an arbiter gets to resolve a dispute (7 days), resolve_by is pushed out if needed
+pub const ARBITRATION_PERIOD: i64 = 7 * 24 * 60 * 60;
+
+// Bond posted to dispute a pending outcome, in basis points of the pot (5%)
+pub const DISPUTE_BOND_BPS: u64 = 500;
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// Highest protocol fee the admin can configure, in basis points (10%)
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 1_000;
+
+// Lamports paid out of the treasury to whoever cranks reclaim_expired_bet (capped at the treasury balance)
+pub const RECLAIM_EXPIRED_BET_INCENTIVE: u64 = 1_000_000;
+
+
+// Current Profile layout - older profiles are brought up to it with migrate_profile
+pub const PROFILE_VERSION: u8 = 6;
+
+// Current Bet layout - older bets are brought up to it with migrate_bet
+pub const BET_VERSION: u8 = 2;
 
+// Time a referee gets to resolve a bet brought up to version 2 by migrate_bet, counted from
+// expiry (or the migration, if later) - version 1 bets had no resolve_by
+pub const MIGRATED_BET_RESOLUTION_PERIOD: i64 = 7 * 24 * 60 * 60;
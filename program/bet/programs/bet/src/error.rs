@@ -5,7 +5,7 @@ pub enum BetError {
     #[msg("Invalid referee type.")]
     InvalidRefereeType,
     
-    #[msg("Invalid odds. Both values must be greater than 0.")]
+    #[msg("Invalid odds for the quoted format")]
     InvalidOdds,
     
     #[msg("Invalid expiration time. Must be in the future.")]
@@ -37,5 +37,160 @@ pub enum BetError {
     
     #[msg("Profile owner does not match expected wallet.")]
     InvalidProfileOwner,
+    
+    #[msg("Bet has not expired yet.")]
+    BetNotExpired,
+    
+    #[msg("Invalid resolution deadline. Must not be before the bet expires.")]
+    InvalidResolutionDeadline,
+    
+    #[msg("Resolution deadline has passed.")]
+    ResolutionDeadlinePassed,
+    
+    #[msg("Resolution deadline has not passed yet.")]
+    ResolutionDeadlineNotReached,
+    
+    #[msg("Invalid bet outcome.")]
+    InvalidOutcome,
+    
+    #[msg("Invalid dispute fallback.")]
+    InvalidDisputeFallback,
+    
+    #[msg("Invalid arbiter. Arbiter fallback needs an arbiter who is not a party to the bet.")]
+    InvalidArbiter,
+    
+    #[msg("Outcome has already been proposed.")]
+    OutcomeAlreadyProposed,
+    
+    #[msg("Invalid challenge window. Must not be negative and needs an arbiter fallback.")]
+    InvalidChallengeWindow,
+    
+    #[msg("Challenge window is still open.")]
+    ChallengeWindowOpen,
+    
+    #[msg("Challenge window has closed.")]
+    ChallengeWindowClosed,
+    
+    #[msg("Invalid oracle price feed.")]
+    InvalidOracleFeed,
+    
+    #[msg("Invalid oracle terms.")]
+    InvalidOracleTerms,
+    
+    #[msg("Oracle observation time has not been reached.")]
+    OracleObservationNotReached,
+    
+    #[msg("Oracle price is stale.")]
+    StaleOraclePrice,
+    
+    #[msg("Oracle price confidence interval is too wide.")]
+    OracleConfidenceTooWide,
+    
+    #[msg("Invalid resolver program. Smart Contract bets need an executable resolver program and a resolver state account.")]
+    InvalidResolverProgram,
+    
+    #[msg("Invalid mint. Must match the mint the bet was created with.")]
+    InvalidMint,
+    
+    #[msg("Invalid token account for this bet.")]
+    InvalidTokenAccount,
+    
+    #[msg("Token transfer did not deliver the expected amount.")]
+    TokenTransferMismatch,
+    
+    #[msg("Invalid protocol fee. Exceeds the maximum fee.")]
+    InvalidProtocolFee,
+    
+    #[msg("Insufficient fees in the fee vault.")]
+    InsufficientFees,
+    
+    #[msg("Invalid referee fee. Only third-party referees can be paid, and the fee must fit in the pot.")]
+    InvalidRefereeFee,
+    
+    #[msg("Referee has not accepted the role yet.")]
+    RefereeNotAccepted,
+    
+    #[msg("Invalid referee panel. Needs 1 to 5 distinct referees other than the creator, and a majority threshold.")]
+    InvalidRefereePanel,
+    
+    #[msg("Referee has already voted on this bet.")]
+    AlreadyVoted,
+    
+    #[msg("Invalid bet visibility. Private bets need a recipient.")]
+    InvalidBetAvailableTo,
+    
+    #[msg("Bet terms changed since they were shown to the acceptor.")]
+    TermsChanged,
+    
+    #[msg("Counter-offer does not belong to this bet.")]
+    InvalidCounterOffer,
+    
+    #[msg("Counter-offer has expired.")]
+    CounterOfferExpired,
+    
+    #[msg("Bet still has open counter-offers. Close them first.")]
+    CounterOffersOpen,
+    
+    #[msg("No early settlement has been proposed.")]
+    NoSettlementProposed,
+    
+    #[msg("Settlement differs from the one shown, or was proposed by the same party.")]
+    SettlementMismatch,
+    
+    #[msg("Invalid position holder. Pass the wallet currently holding the position.")]
+    InvalidPositionHolder,
+    
+    #[msg("Pool bets are filled with fill_bet and resolved with resolve_pool_bet")]
+    PoolBet,
+    
+    #[msg("Only pool bets can be filled")]
+    NotPoolBet,
+    
+    #[msg("Fill amount must be positive and no more than the unfilled stake")]
+    InvalidFillAmount,
+    
+    #[msg("Fill does not belong to this bet")]
+    InvalidFill,
+    
+    #[msg("Pool bet still has fills to pay out with claim_fill")]
+    FillsOpen,
+    
+    #[msg("Markets need between 2 and 8 named outcomes")]
+    InvalidMarketOutcomes,
+    
+    #[msg("Outcome is not on this market")]
+    InvalidMarketOutcome,
+    
+    #[msg("Stake amount must be positive")]
+    InvalidStakeAmount,
+    
+    #[msg("Stake does not belong to this market")]
+    InvalidMarketStake,
+    
+    #[msg("Market still has stakes to pay out with claim_market_stake")]
+    StakesOpen,
+    
+    #[msg("Parlays need 2 to 5 distinct, undecided leg bets, passed in order")]
+    InvalidParlay,
+    
+    #[msg("Parlay odds must be at least even and no longer than the fair odds for the number of legs")]
+    InvalidParlayOdds,
+    
+    #[msg("Parlay legs haven't decided the outcome yet")]
+    ParlayLegsPending,
+    
+    #[msg("Bet is a leg of a parlay that hasn't been deleted")]
+    LinkedToParlay,
+    
+    #[msg("Stake at these odds rounds down to zero")]
+    StakeRoundsToZero,
+    
+    #[msg("Treasury doesn't hold enough to cover the payout")]
+    TreasuryUnderfunded,
+    
+    #[msg("This would take the user past their maximum exposure")]
+    ExposureLimitExceeded,
+    
+    #[msg("Parlay legs must be bets the parlay's creator made")]
+    ParlayLegNotOwned,
 }
-
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::state::bet::{Bet, BetStatus, BetAvailableTo};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::bet::{Bet, BetStatus, BetAvailableTo, PositionSide};
 use crate::state::profile::Profile;
 
 #[derive(Accounts)]
@@ -10,6 +11,14 @@ pub struct AcceptBet<'info> {
     /// CHECK: Creator is validated by checking bet.creator matches this account
     pub creator: AccountInfo<'info>,
     
+    #[account(
+        mut,
+        seeds = [b"username-", creator_profile.name.as_ref()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.wallet == creator.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub creator_profile: Account<'info, Profile>,
+    
     #[account(
         mut,
         seeds = [b"username-", acceptor_profile.name.as_ref()],
@@ -22,6 +31,9 @@ pub struct AcceptBet<'info> {
         mut,
         constraint = bet.creator == creator.key() @ crate::error::BetError::InvalidBetCreator,
         constraint = bet.creator != acceptor.key() @ crate::error::BetError::CannotAcceptOwnBet,
+        constraint = !bet.partial_fills @ crate::error::BetError::PoolBet,
+        constraint = bet.panel.member_index(&acceptor.key()).is_none() @ crate::error::BetError::Unauthorized,
+        constraint = bet.status != BetStatus::PendingReferee as u8 @ crate::error::BetError::RefereeNotAccepted,
         constraint = bet.status == BetStatus::Open as u8 @ crate::error::BetError::InvalidBetStatus,
         constraint = bet.acceptor.is_none() @ crate::error::BetError::BetAlreadyAccepted
     )]
@@ -37,9 +49,35 @@ pub struct AcceptBet<'info> {
     pub treasury: UncheckedAccount<'info>,
     
     pub system_program: Program<'info, System>,
+    
+    // SPL bets only - leave these out for SOL bets
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(mut)]
+    pub acceptor_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
-pub fn accept_bet(ctx: Context<AcceptBet>) -> Result<()> {
+pub fn accept_bet(
+    ctx: Context<AcceptBet>,
+    expected_acceptor_bet_amount: u64,
+    expected_terms_revision: u32,
+) -> Result<()> {
+    let treasury = crate::treasury::BetTreasury::new(
+        &ctx.accounts.bet,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+    let acceptor_token_account = ctx.accounts.acceptor_token_account.as_ref().map(|account| account.to_account_info());
+    
     let bet = &mut ctx.accounts.bet;
     let acceptor_profile = &mut ctx.accounts.acceptor_profile;
     let clock = Clock::get()?;
@@ -50,6 +88,12 @@ pub fn accept_bet(ctx: Context<AcceptBet>) -> Result<()> {
         crate::error::BetError::BetExpired
     );
     
+    // The arbiter of a bet can't also be a party to it
+    require!(
+        bet.arbiter != Some(ctx.accounts.acceptor.key()),
+        crate::error::BetError::InvalidArbiter
+    );
+    
     // If bet is private, only the private_bet_recipient can accept it
     if bet.bet_available_to == BetAvailableTo::Private as u8 {
         require!(
@@ -63,27 +107,26 @@ pub fn accept_bet(ctx: Context<AcceptBet>) -> Result<()> {
     }
     
     // Calculate acceptor's bet amount: creator bet * (oddsWin / oddsLose)
-    // This ensures the payout ratios are correct
-    let acceptor_bet_amount = bet.bet_amount
-        .checked_mul(bet.odds_win)
-        .and_then(|x| x.checked_div(bet.odds_lose))
-        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
-    
-    // Transfer acceptor's calculated bet amount to treasury using system program
-    anchor_lang::solana_program::program::invoke(
-        &anchor_lang::solana_program::system_instruction::transfer(
-            ctx.accounts.acceptor.key,
-            ctx.accounts.treasury.key,
-            acceptor_bet_amount,
-        ),
-        &[
-            ctx.accounts.acceptor.to_account_info(),
-            ctx.accounts.treasury.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
+    let acceptor_bet_amount = bet.acceptor_bet_amount()?;
+    
+    // Terms must be exactly what the acceptor was shown - the creator can edit them with
+    // update_bet, so a transaction landing after an edit must not charge a different stake
+    require!(
+        bet.terms_revision == expected_terms_revision
+            && acceptor_bet_amount == expected_acceptor_bet_amount,
+        crate::error::BetError::TermsChanged
+    );
+    
+    // Transfer acceptor's calculated bet amount to treasury (lamports, or tokens into the vault for SPL bets)
+    treasury.deposit(
+        &ctx.accounts.acceptor.to_account_info(),
+        acceptor_token_account,
+        acceptor_bet_amount,
     )?;
     
+    bet.acceptor_deposit = acceptor_bet_amount;
     bet.acceptor = Some(ctx.accounts.acceptor.key());
+    bet.acceptor_position_holder = Some(ctx.accounts.acceptor.key());
     bet.acceptor_username = acceptor_profile.name;
     bet.status = BetStatus::Accepted as u8;
     bet.accepted_at = Some(clock.unix_timestamp);
@@ -91,6 +134,13 @@ pub fn accept_bet(ctx: Context<AcceptBet>) -> Result<()> {
     // Increment acceptor's accepted bet count
     acceptor_profile.total_bets_accepted_count += 1;
     
+    // Both stakes are now locked in an accepted bet
+    acceptor_profile.active_bet_count += 1;
+    acceptor_profile.lock_stake(PositionSide::Acceptor, acceptor_bet_amount)?;
+    let creator_profile = &mut ctx.accounts.creator_profile;
+    creator_profile.open_bet_count = creator_profile.open_bet_count.saturating_sub(1);
+    creator_profile.active_bet_count += 1;
+    
     Ok(())
 }
 
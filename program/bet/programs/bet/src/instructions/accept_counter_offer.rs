@@ -0,0 +1,203 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::bet::{Bet, BetStatus, BetAvailableTo, PositionSide};
+use crate::state::counter_offer::CounterOffer;
+use crate::state::profile::Profile;
+use crate::treasury::BetTreasury;
+
+#[derive(Accounts)]
+pub struct AcceptCounterOffer<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"username-", creator_profile.name.as_ref()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.wallet == creator.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub creator_profile: Account<'info, Profile>,
+
+    /// CHECK: Offerer is validated by checking counter_offer.offerer matches this account (mut to receive rent)
+    #[account(mut)]
+    pub offerer: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"username-", offerer_profile.name.as_ref()],
+        bump = offerer_profile.bump,
+        constraint = offerer_profile.wallet == offerer.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub offerer_profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        constraint = bet.creator == creator.key() @ crate::error::BetError::Unauthorized,
+        constraint = bet.status == BetStatus::Open as u8 @ crate::error::BetError::InvalidBetStatus,
+        constraint = bet.acceptor.is_none() @ crate::error::BetError::BetAlreadyAccepted
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// CHECK: Treasury PDA for holding bet funds (must exist from create_bet)
+    #[account(
+        mut,
+        seeds = [b"bet-treasury-", bet.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Treasury account
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = offerer,  // Close the offer and send rent back to the offerer
+        constraint = counter_offer.bet == bet.key() @ crate::error::BetError::InvalidCounterOffer,
+        constraint = counter_offer.offerer == offerer.key() @ crate::error::BetError::InvalidCounterOffer
+    )]
+    pub counter_offer: Account<'info, CounterOffer>,
+
+    /// CHECK: Escrow PDA holding the offerer's stake
+    #[account(
+        mut,
+        seeds = [b"counter-offer-escrow-", counter_offer.key().as_ref()],
+        bump = counter_offer.escrow_bump
+    )]
+    /// CHECK: Escrow account
+    pub escrow: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // SPL bets only - leave these out for SOL bets (mint is mut to harvest withheld transfer fees)
+    #[account(mut)]
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub escrow_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub offerer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn accept_counter_offer(ctx: Context<AcceptCounterOffer>) -> Result<()> {
+    let treasury = BetTreasury::new(
+        &ctx.accounts.bet,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+    let escrow = BetTreasury::for_counter_offer(
+        &ctx.accounts.bet,
+        &ctx.accounts.counter_offer,
+        ctx.accounts.escrow.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.escrow_vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+
+    let bet = &mut ctx.accounts.bet;
+    let counter_offer = &ctx.accounts.counter_offer;
+    let offerer = ctx.accounts.offerer.to_account_info();
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp < counter_offer.expires_at,
+        crate::error::BetError::CounterOfferExpired
+    );
+    require!(
+        bet.expires_at > clock.unix_timestamp,
+        crate::error::BetError::BetExpired
+    );
+
+    // Visibility or arbiter may have changed with update_bet since the offer was made
+    require!(
+        bet.arbiter != Some(offerer.key()),
+        crate::error::BetError::InvalidArbiter
+    );
+    if bet.bet_available_to == BetAvailableTo::Private as u8 {
+        require!(
+            bet.private_bet_recipient == Some(offerer.key()),
+            crate::error::BetError::Unauthorized
+        );
+    }
+
+    // Rewrite the bet to the offered terms
+    let previous_amount = bet.creator_deposit;
+    bet.bet_amount = counter_offer.bet_amount;
+    bet.odds_win = counter_offer.odds_win;
+    bet.odds_lose = counter_offer.odds_lose;
+    bet.terms_revision = bet.terms_revision
+        .checked_add(1)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+
+    require!(
+        bet.acceptor_bet_amount()? == counter_offer.offerer_bet_amount,
+        crate::error::BetError::InvalidCounterOffer
+    );
+
+    // A flat referee fee can't be more than the pot it is paid from
+    require!(
+        bet.referee_fee_amount()? <= bet.pot()?,
+        crate::error::BetError::InvalidRefereeFee
+    );
+
+    // Top up the creator's stake or hand back the difference
+    let creator = ctx.accounts.creator.to_account_info();
+    let creator_token_account = ctx.accounts.creator_token_account.as_ref().map(|account| account.to_account_info());
+    if bet.bet_amount > previous_amount {
+        treasury.deposit(&creator, creator_token_account, bet.bet_amount - previous_amount)?;
+    } else if bet.bet_amount < previous_amount {
+        treasury.pay(&creator, creator_token_account, previous_amount - bet.bet_amount)?;
+    }
+    bet.creator_deposit = bet.bet_amount;
+
+    // Move the offerer's escrowed stake into the bet treasury, then empty and close the escrow
+    escrow.move_to(&treasury, counter_offer.offerer_bet_amount)?;
+    bet.acceptor_deposit = counter_offer.offerer_bet_amount;
+    escrow.pay(
+        &offerer,
+        ctx.accounts.offerer_token_account.as_ref().map(|account| account.to_account_info()),
+        escrow.balance()?,
+    )?;
+    escrow.close_vault(&offerer)?;
+
+    bet.acceptor = Some(offerer.key());
+    bet.acceptor_position_holder = Some(offerer.key());
+    bet.acceptor_username = ctx.accounts.offerer_profile.name;
+    bet.status = BetStatus::Accepted as u8;
+    bet.accepted_at = Some(clock.unix_timestamp);
+    bet.open_counter_offer_count = bet.open_counter_offer_count
+        .checked_sub(1)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+
+    // Increment offerer's accepted bet count, same as accept_bet
+    let offerer_profile = &mut ctx.accounts.offerer_profile;
+    offerer_profile.total_bets_accepted_count += 1;
+
+    // The offerer's stake is locked from here, same as accept_bet, and the creator's is swapped
+    // for the offered amount - either side raising its stake is checked against its max_exposure
+    offerer_profile.active_bet_count += 1;
+    offerer_profile.lock_stake(PositionSide::Acceptor, counter_offer.offerer_bet_amount)?;
+    let creator_profile = &mut ctx.accounts.creator_profile;
+    creator_profile.open_bet_count = creator_profile.open_bet_count.saturating_sub(1);
+    creator_profile.active_bet_count += 1;
+    if bet.bet_amount > previous_amount {
+        creator_profile.lock_stake(PositionSide::Creator, bet.bet_amount - previous_amount)?;
+    } else {
+        creator_profile.unlock_stake(PositionSide::Creator, previous_amount - bet.bet_amount);
+    }
+
+    // The counter-offer account will be closed automatically by Anchor's `close = offerer` constraint
+
+    Ok(())
+}
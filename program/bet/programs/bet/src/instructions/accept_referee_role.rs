@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::bet::{Bet, BetStatus};
+
+#[derive(Accounts)]
+pub struct AcceptRefereeRole<'info> {
+    pub referee: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = bet.referee == referee.key() @ crate::error::BetError::Unauthorized,
+        constraint = bet.status == BetStatus::PendingReferee as u8 @ crate::error::BetError::InvalidBetStatus
+    )]
+    pub bet: Account<'info, Bet>,
+}
+
+pub fn accept_referee_role(ctx: Context<AcceptRefereeRole>) -> Result<()> {
+    let bet = &mut ctx.accounts.bet;
+    let clock = Clock::get()?;
+    
+    // No point going live once the bet can no longer be accepted
+    require!(
+        bet.expires_at > clock.unix_timestamp,
+        crate::error::BetError::BetExpired
+    );
+    
+    // Accepting also agrees to any referee fee set on the bet
+    bet.status = BetStatus::Open as u8;
+    bet.referee_accepted_at = Some(clock.unix_timestamp);
+    
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::instructions::resolve_bet::position_payee;
+use crate::state::bet::{Bet, BetStatus, PositionSide};
+use crate::state::profile::{BetResult, Profile};
+use crate::treasury::BetTreasury;
+
+#[derive(Accounts)]
+pub struct AcceptSettlement<'info> {
+    pub signer: Signer<'info>,
+
+    /// CHECK: Creator is validated by checking bet.creator matches this account (mut for SOL transfer)
+    #[account(mut)]
+    pub creator: AccountInfo<'info>,
+
+    /// CHECK: Acceptor is validated by checking bet.acceptor matches this account (mut for SOL transfer)
+    #[account(mut)]
+    pub acceptor: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"username-", creator_profile.name.as_ref()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.wallet == creator.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub creator_profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        seeds = [b"username-", acceptor_profile.name.as_ref()],
+        bump = acceptor_profile.bump,
+        constraint = acceptor_profile.wallet == acceptor.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub acceptor_profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        constraint = bet.creator == creator.key() @ crate::error::BetError::InvalidBetCreator,
+        constraint = bet.status == BetStatus::Accepted as u8 @ crate::error::BetError::InvalidBetStatus,
+        constraint = bet.acceptor == Some(acceptor.key()) @ crate::error::BetError::BetNotAccepted
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// CHECK: Treasury PDA for holding bet funds (must exist from create_bet)
+    #[account(
+        mut,
+        seeds = [b"bet-treasury-", bet.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Treasury account
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Current holder of the creator's position, validated against bet.creator_position_holder (only needed once it has been transferred)
+    #[account(mut)]
+    pub creator_position_holder: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Current holder of the acceptor's position, validated against bet.acceptor_position_holder (only needed once it has been transferred)
+    #[account(mut)]
+    pub acceptor_position_holder: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+
+    // SPL bets only - leave these out for SOL bets
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub acceptor_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+// Splits the treasury as proposed by the other party and ends the bet. No protocol or
+// referee fee is taken, nobody won and the referee never ruled.
+pub fn accept_settlement(ctx: Context<AcceptSettlement>, expected_creator_amount: u64) -> Result<()> {
+    let treasury = BetTreasury::new(
+        &ctx.accounts.bet,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+
+    let bet = &mut ctx.accounts.bet;
+    let signer = ctx.accounts.signer.key();
+    let clock = Clock::get()?;
+
+    let proposer = bet.settlement_proposer.ok_or(crate::error::BetError::NoSettlementProposed)?;
+    require!(
+        signer == bet.creator_position_holder || Some(signer) == bet.acceptor_position_holder,
+        crate::error::BetError::Unauthorized
+    );
+
+    // Only the other side can accept, and only the split they were shown
+    require!(
+        signer != proposer && bet.settlement_creator_amount == expected_creator_amount,
+        crate::error::BetError::SettlementMismatch
+    );
+
+    require!(
+        clock.unix_timestamp < bet.resolve_by,
+        crate::error::BetError::ResolutionDeadlinePassed
+    );
+
+    // Shares go to whoever holds each side's position now, stats stay with the original parties
+    let creator_payee = position_payee(
+        &ctx.accounts.creator.to_account_info(),
+        ctx.accounts.creator_position_holder.as_ref().map(|account| account.as_ref()),
+        Some(bet.creator_position_holder),
+    )?;
+    let acceptor_payee = position_payee(
+        &ctx.accounts.acceptor.to_account_info(),
+        ctx.accounts.acceptor_position_holder.as_ref().map(|account| account.as_ref()),
+        bet.acceptor_position_holder,
+    )?;
+
+    // The recorded stakes are split as agreed, the acceptor's share is whatever the creator's isn't
+    let creator_staked = bet.creator_deposit;
+    let acceptor_staked = bet.acceptor_deposit;
+    let creator_amount = bet.settlement_creator_amount;
+    let acceptor_amount = bet.deposits()?
+        .checked_sub(creator_amount)
+        .ok_or(crate::error::BetError::SettlementMismatch)?;
+    bet.creator_deposit = 0;
+    bet.acceptor_deposit = 0;
+
+    let creator_token_account = ctx.accounts.creator_token_account.as_ref().map(|account| account.to_account_info());
+    treasury.pay(&creator_payee, creator_token_account.clone(), creator_amount)?;
+    treasury.pay(
+        &acceptor_payee,
+        ctx.accounts.acceptor_token_account.as_ref().map(|account| account.to_account_info()),
+        acceptor_amount,
+    )?;
+    treasury.sweep_excess(bet, &creator_payee, creator_token_account)?;
+
+    bet.status = BetStatus::Settled as u8;
+    bet.resolved_at = Some(clock.unix_timestamp);
+    bet.winner = None;
+
+    // Settlements aren't wins or losses - record them separately with what each side got back
+    let creator_profile = &mut ctx.accounts.creator_profile;
+    creator_profile.record_result(PositionSide::Creator, BetResult::Settled, creator_staked, creator_amount)?;
+    creator_profile.active_bet_count = creator_profile.active_bet_count.saturating_sub(1);
+    creator_profile.unlock_stake(PositionSide::Creator, creator_staked);
+
+    let acceptor_profile = &mut ctx.accounts.acceptor_profile;
+    acceptor_profile.record_result(PositionSide::Acceptor, BetResult::Settled, acceptor_staked, acceptor_amount)?;
+    acceptor_profile.active_bet_count = acceptor_profile.active_bet_count.saturating_sub(1);
+    acceptor_profile.unlock_stake(PositionSide::Acceptor, acceptor_staked);
+
+    Ok(())
+}
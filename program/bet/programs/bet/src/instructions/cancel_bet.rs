@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::state::bet::{Bet, BetStatus};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::bet::{Bet, BetStatus, PositionSide};
 use crate::state::profile::Profile;
 
 #[derive(Accounts)]
@@ -18,7 +19,7 @@ pub struct CancelBet<'info> {
     #[account(
         mut,
         constraint = bet.creator == creator.key() @ crate::error::BetError::Unauthorized,
-        constraint = bet.status == BetStatus::Open as u8 @ crate::error::BetError::InvalidBetStatus
+        constraint = bet.status == BetStatus::Open as u8 || bet.status == BetStatus::PendingReferee as u8 @ crate::error::BetError::InvalidBetStatus
     )]
     pub bet: Account<'info, Bet>,
     
@@ -32,49 +33,45 @@ pub struct CancelBet<'info> {
     pub treasury: UncheckedAccount<'info>,
     
     pub system_program: Program<'info, System>,
+    
+    // SPL bets only - leave these out for SOL bets
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(mut)]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 pub fn cancel_bet(ctx: Context<CancelBet>) -> Result<()> {
-    // Get bet key and treasury bump before mutable borrow
-    let bet_key = ctx.accounts.bet.key();
-    let treasury_bump = ctx.bumps.treasury;
+    let treasury = crate::treasury::BetTreasury::new(
+        &ctx.accounts.bet,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
     
     let bet = &mut ctx.accounts.bet;
     
-    // Can only cancel if bet hasn't been accepted (status check already ensures it's Open)
+    // Can only cancel if bet hasn't been accepted or filled (status check already ensures it's Open or waiting on the referee)
     require!(
-        bet.acceptor.is_none(),
+        bet.acceptor.is_none() && bet.filled_amount == 0,
         crate::error::BetError::BetAlreadyAccepted
     );
     
-    // Get treasury balance (all SOL in treasury)
-    let treasury_balance = ctx.accounts.treasury.lamports();
-    
-    // Transfer all SOL from treasury back to creator using system program
-    // Must use invoke_signed because treasury is a PDA and needs program signature
-    if treasury_balance > 0 {
-        // Prepare seeds for PDA signing
-        let seeds = &[
-            b"bet-treasury-",
-            bet_key.as_ref(),
-            &[treasury_bump],
-        ];
-        let signer_seeds = &[&seeds[..]];
-        
-        anchor_lang::solana_program::program::invoke_signed(
-            &anchor_lang::solana_program::system_instruction::transfer(
-                ctx.accounts.treasury.key,
-                ctx.accounts.creator.key,
-                treasury_balance,
-            ),
-            &[
-                ctx.accounts.treasury.to_account_info(),
-                ctx.accounts.creator.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            signer_seeds,
-        )?;
-    }
+    // Return the creator's stake, and anything else sent to the treasury
+    let creator = ctx.accounts.creator.to_account_info();
+    let creator_token_account = ctx.accounts.creator_token_account.as_ref().map(|account| account.to_account_info());
+    let refund = bet.creator_deposit;
+    bet.creator_deposit = 0;
+    treasury.pay(&creator, creator_token_account.clone(), refund)?;
+    treasury.sweep_excess(bet, &creator, creator_token_account)?;
     
     bet.status = BetStatus::Cancelled as u8;
     
@@ -83,6 +80,8 @@ pub fn cancel_bet(ctx: Context<CancelBet>) -> Result<()> {
     profile.cancelled_bet_count = profile.cancelled_bet_count
         .checked_add(1)
         .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+    profile.open_bet_count = profile.open_bet_count.saturating_sub(1);
+    profile.unlock_stake(PositionSide::Creator, refund);
     
     Ok(())
 }
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::instructions::resolve_bet::Settlement;
+use crate::treasury::BetTreasury;
+use crate::state::bet::{Bet, BetStatus, BetOutcome, RefereeType};
+use crate::state::config::Config;
+use crate::state::profile::Profile;
+
+#[derive(Accounts)]
+pub struct CastRefereeVote<'info> {
+    pub voter: Signer<'info>,
+
+    /// CHECK: Creator is validated by checking bet.creator matches this account (mut for SOL transfer)
+    #[account(mut)]
+    pub creator: AccountInfo<'info>,
+
+    /// CHECK: Acceptor is validated by checking bet.acceptor matches this account (mut for SOL transfer)
+    #[account(mut)]
+    pub acceptor: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"username-", creator_profile.name.as_ref()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.wallet == creator.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub creator_profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        seeds = [b"username-", acceptor_profile.name.as_ref()],
+        bump = acceptor_profile.bump,
+        constraint = acceptor_profile.wallet == acceptor.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub acceptor_profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        constraint = bet.creator == creator.key() @ crate::error::BetError::InvalidBetCreator,
+        constraint = bet.status == BetStatus::Accepted as u8 @ crate::error::BetError::InvalidBetStatus,
+        constraint = bet.acceptor == Some(acceptor.key()) @ crate::error::BetError::BetNotAccepted,
+        constraint = bet.referee_type == RefereeType::Panel as u8 @ crate::error::BetError::InvalidRefereeType
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// CHECK: Treasury PDA for holding bet funds (must exist from create_bet)
+    #[account(
+        mut,
+        seeds = [b"bet-treasury-", bet.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Treasury account
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Fee vault PDA receiving the protocol fee
+    #[account(
+        mut,
+        seeds = [b"fee-vault"],
+        bump = config.fee_vault_bump
+    )]
+    pub fee_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Current holder of the creator's position, validated against bet.creator_position_holder (only needed once it has been transferred)
+    #[account(mut)]
+    pub creator_position_holder: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Current holder of the acceptor's position, validated against bet.acceptor_position_holder (only needed once it has been transferred)
+    #[account(mut)]
+    pub acceptor_position_holder: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+
+    // SPL bets only - leave these out for SOL bets
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub acceptor_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub fee_vault_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn cast_referee_vote(ctx: Context<CastRefereeVote>, outcome: u8) -> Result<()> {
+    // Validate outcome - Pending is not a valid vote
+    let outcome = BetOutcome::try_from(outcome)?;
+    require!(
+        outcome != BetOutcome::Pending,
+        crate::error::BetError::InvalidOutcome
+    );
+
+    let bet = &mut ctx.accounts.bet;
+    let clock = Clock::get()?;
+
+    // Once the resolution deadline passes the bet can only be refunded
+    require!(
+        clock.unix_timestamp < bet.resolve_by,
+        crate::error::BetError::ResolutionDeadlinePassed
+    );
+
+    // Record the vote for whichever referee signed - each referee votes once
+    let index = bet.panel
+        .member_index(&ctx.accounts.voter.key())
+        .ok_or(crate::error::BetError::Unauthorized)?;
+    require!(
+        bet.panel.votes[index] == BetOutcome::Pending as u8,
+        crate::error::BetError::AlreadyVoted
+    );
+    bet.panel.votes[index] = outcome as u8;
+
+    // Settle once M referees agree. If the votes are split so that no outcome can reach
+    // the threshold any more, the bet is voided and both sides get their stake back
+    let outcome = match bet.panel.decided_outcome() {
+        Some(outcome) => outcome,
+        None if bet.panel.is_deadlocked() => BetOutcome::Void,
+        None => return Ok(()),
+    };
+
+    // Pay out through the same path as resolve_bet
+    let treasury = BetTreasury::new(
+        &ctx.accounts.bet,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+
+    Settlement {
+        creator: ctx.accounts.creator.to_account_info(),
+        acceptor: ctx.accounts.acceptor.to_account_info(),
+        creator_position_holder: ctx.accounts.creator_position_holder.as_ref().map(|account| account.to_account_info()),
+        acceptor_position_holder: ctx.accounts.acceptor_position_holder.as_ref().map(|account| account.to_account_info()),
+        creator_profile: &mut ctx.accounts.creator_profile,
+        acceptor_profile: &mut ctx.accounts.acceptor_profile,
+        bet: &mut ctx.accounts.bet,
+        creator_token_account: ctx.accounts.creator_token_account.as_ref().map(|account| account.to_account_info()),
+        acceptor_token_account: ctx.accounts.acceptor_token_account.as_ref().map(|account| account.to_account_info()),
+        treasury,
+        fee_bps: ctx.accounts.config.fee_bps,
+        fee_vault: ctx.accounts.fee_vault.to_account_info(),
+        fee_vault_token_account: ctx.accounts.fee_vault_token_account.as_ref().map(|account| account.to_account_info()),
+        referee: None,
+        referee_token_account: None,
+    }
+    .settle(outcome)
+}
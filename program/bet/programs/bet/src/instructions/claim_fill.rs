@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::math::bps_of;
+use crate::state::bet::{Bet, BetStatus, BetOutcome, PositionSide};
+use crate::state::config::Config;
+use crate::state::fill::Fill;
+use crate::state::profile::{BetResult, Profile};
+use crate::treasury::BetTreasury;
+
+#[derive(Accounts)]
+pub struct ClaimFill<'info> {
+    /// CHECK: Anyone can call this instruction (permissionless)
+    pub caller: Signer<'info>,
+
+    /// CHECK: Acceptor is validated by checking fill.acceptor matches this account (mut for payout and rent)
+    #[account(mut)]
+    pub acceptor: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"username-", acceptor_profile.name.as_ref()],
+        bump = acceptor_profile.bump,
+        constraint = acceptor_profile.wallet == acceptor.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub acceptor_profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        constraint = bet.partial_fills @ crate::error::BetError::NotPoolBet,
+        constraint = bet.status == BetStatus::Resolved as u8 || bet.status == BetStatus::Voided as u8 @ crate::error::BetError::InvalidBetStatus
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(
+        mut,
+        close = acceptor,  // Close the fill and send rent back to the acceptor
+        constraint = fill.bet == bet.key() @ crate::error::BetError::InvalidFill,
+        constraint = fill.acceptor == acceptor.key() @ crate::error::BetError::InvalidFill
+    )]
+    pub fill: Account<'info, Fill>,
+
+    /// CHECK: Treasury PDA for holding bet funds (must exist from create_bet)
+    #[account(
+        mut,
+        seeds = [b"bet-treasury-", bet.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Treasury account
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Fee vault PDA receiving the protocol fee
+    #[account(
+        mut,
+        seeds = [b"fee-vault"],
+        bump = config.fee_vault_bump
+    )]
+    pub fee_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // SPL bets only - leave these out for SOL bets
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub acceptor_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub fee_vault_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+// Pay out one fill of a resolved pool bet: its stake plus the creator's stake it matched if
+// the acceptors won, its stake back on a push or void, nothing if the creator won
+pub fn claim_fill(ctx: Context<ClaimFill>) -> Result<()> {
+    let treasury = BetTreasury::new(
+        &ctx.accounts.bet,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+    let acceptor = ctx.accounts.acceptor.to_account_info();
+    let acceptor_token_account = ctx.accounts.acceptor_token_account.as_ref().map(|account| account.to_account_info());
+
+    let bet = &mut ctx.accounts.bet;
+    let fill = &ctx.accounts.fill;
+    let acceptor_profile = &mut ctx.accounts.acceptor_profile;
+    let outcome = BetOutcome::try_from(bet.outcome)?;
+
+    match outcome {
+        BetOutcome::CreatorWins => {
+            // The creator already took the whole treasury in resolve_pool_bet
+            acceptor_profile.record_result(PositionSide::Acceptor, BetResult::Loss, fill.acceptor_amount, 0)?;
+        }
+        BetOutcome::AcceptorWins => {
+            // Protocol fee on this fill's share of the pot, at the rate recorded when the pool was resolved
+            let payout = fill.creator_amount
+                .checked_add(fill.acceptor_amount)
+                .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+            let protocol_fee = bps_of(payout, bet.protocol_fee_bps as u64)?;
+            bet.creator_deposit = bet.creator_deposit
+                .checked_sub(fill.creator_amount)
+                .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+            bet.acceptor_deposit = bet.acceptor_deposit
+                .checked_sub(fill.acceptor_amount)
+                .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+
+            treasury.pay(
+                &ctx.accounts.fee_vault.to_account_info(),
+                ctx.accounts.fee_vault_token_account.as_ref().map(|account| account.to_account_info()),
+                protocol_fee,
+            )?;
+            bet.protocol_fee = bet.protocol_fee
+                .checked_add(protocol_fee)
+                .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+
+            let winnings = payout
+                .checked_sub(protocol_fee)
+                .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+            treasury.pay(&acceptor, acceptor_token_account, winnings)?;
+
+            acceptor_profile.record_result(PositionSide::Acceptor, BetResult::Win, fill.acceptor_amount, winnings)?;
+        }
+        _ => {
+            // Push or Void - the fill's stake goes back to the acceptor
+            bet.acceptor_deposit = bet.acceptor_deposit
+                .checked_sub(fill.acceptor_amount)
+                .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+            treasury.pay(&acceptor, acceptor_token_account, fill.acceptor_amount)?;
+
+            // Refunds past resolve_by aren't counted, same as refund_unresolved_bet
+            if outcome == BetOutcome::Push {
+                acceptor_profile.record_result(PositionSide::Acceptor, BetResult::Push, fill.acceptor_amount, fill.acceptor_amount)?;
+            } else if bet.status == BetStatus::Resolved as u8 {
+                acceptor_profile.record_result(PositionSide::Acceptor, BetResult::Void, fill.acceptor_amount, fill.acceptor_amount)?;
+            }
+        }
+    }
+
+    acceptor_profile.active_bet_count = acceptor_profile.active_bet_count.saturating_sub(1);
+    acceptor_profile.unlock_stake(PositionSide::Acceptor, fill.acceptor_amount);
+
+    bet.open_fill_count = bet.open_fill_count
+        .checked_sub(1)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+
+    // The fill account will be closed automatically by Anchor's `close = acceptor` constraint
+
+    Ok(())
+}
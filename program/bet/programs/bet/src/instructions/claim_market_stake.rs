@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::math::pro_rata;
+use crate::state::bet::BetStatus;
+use crate::state::market::{Market, MarketStake};
+use crate::treasury::BetTreasury;
+
+#[derive(Accounts)]
+pub struct ClaimMarketStake<'info> {
+    /// CHECK: Anyone can call this instruction (permissionless)
+    pub caller: Signer<'info>,
+
+    /// CHECK: Staker is validated by checking stake.staker matches this account (mut for payout and rent)
+    #[account(mut)]
+    pub staker: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = market.status == BetStatus::Resolved as u8 || market.status == BetStatus::Voided as u8 @ crate::error::BetError::InvalidBetStatus
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        close = staker,  // Close the stake and send rent back to the staker
+        constraint = stake.market == market.key() @ crate::error::BetError::InvalidMarketStake,
+        constraint = stake.staker == staker.key() @ crate::error::BetError::InvalidMarketStake
+    )]
+    pub stake: Account<'info, MarketStake>,
+
+    /// CHECK: Treasury PDA holding the market's stakes
+    #[account(
+        mut,
+        seeds = [b"market-treasury-", market.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Treasury account
+    pub treasury: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // SPL markets only - leave these out for SOL markets
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub staker_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+// Pay out one stake of a resolved market: a winning stake's share of the pool (after the
+// protocol fee) in proportion to its size, the stake itself if the market was voided, nothing
+// for a losing stake. The stake account is closed either way.
+pub fn claim_market_stake(ctx: Context<ClaimMarketStake>) -> Result<()> {
+    let treasury = BetTreasury::for_market(
+        &ctx.accounts.market,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+
+    let market = &mut ctx.accounts.market;
+    let stake = &ctx.accounts.stake;
+
+    let payout = match market.winning_outcome {
+        None => stake.amount,
+        Some(outcome) if outcome == stake.outcome => {
+            // Rounding down leaves any dust in the treasury, delete_market returns it to the creator
+            let distributable = market.total_pool
+                .checked_sub(market.protocol_fee)
+                .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+            pro_rata(distributable, stake.amount, market.outcome_pools[outcome as usize])?
+        }
+        Some(_) => 0,
+    };
+    treasury.pay(
+        &ctx.accounts.staker.to_account_info(),
+        ctx.accounts.staker_token_account.as_ref().map(|account| account.to_account_info()),
+        payout,
+    )?;
+
+    market.open_stake_count = market.open_stake_count
+        .checked_sub(1)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+
+    // The stake account will be closed automatically by Anchor's `close = staker` constraint
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::bet::{Bet, BetStatus};
+use crate::state::counter_offer::CounterOffer;
+use crate::treasury::BetTreasury;
+
+#[derive(Accounts)]
+pub struct CloseCounterOffer<'info> {
+    /// CHECK: Offerer (withdraw), creator (reject), or anyone once the offer can no longer be accepted
+    pub caller: Signer<'info>,
+
+    /// CHECK: Offerer is validated by checking counter_offer.offerer matches this account (mut for refund)
+    #[account(mut)]
+    pub offerer: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub bet: Account<'info, Bet>,
+
+    #[account(
+        mut,
+        close = offerer,  // Close the offer and send rent back to the offerer
+        constraint = counter_offer.bet == bet.key() @ crate::error::BetError::InvalidCounterOffer,
+        constraint = counter_offer.offerer == offerer.key() @ crate::error::BetError::InvalidCounterOffer
+    )]
+    pub counter_offer: Account<'info, CounterOffer>,
+
+    /// CHECK: Escrow PDA holding the offerer's stake
+    #[account(
+        mut,
+        seeds = [b"counter-offer-escrow-", counter_offer.key().as_ref()],
+        bump = counter_offer.escrow_bump
+    )]
+    /// CHECK: Escrow account
+    pub escrow: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // SPL bets only - leave these out for SOL bets (mint is mut to harvest withheld transfer fees)
+    #[account(mut)]
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(mut)]
+    pub escrow_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub offerer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+// Refunds a counter-offer that won't be accepted: withdrawn by the offerer, rejected by the
+// creator, or closed by anyone once it has expired or the bet is no longer open
+pub fn close_counter_offer(ctx: Context<CloseCounterOffer>) -> Result<()> {
+    let escrow = BetTreasury::for_counter_offer(
+        &ctx.accounts.bet,
+        &ctx.accounts.counter_offer,
+        ctx.accounts.escrow.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.escrow_vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+
+    let bet = &mut ctx.accounts.bet;
+    let caller = ctx.accounts.caller.key();
+    let clock = Clock::get()?;
+
+    require!(
+        caller == ctx.accounts.offerer.key()
+            || caller == bet.creator
+            || clock.unix_timestamp >= ctx.accounts.counter_offer.expires_at
+            || bet.status != BetStatus::Open as u8,
+        crate::error::BetError::Unauthorized
+    );
+
+    // Return everything in the escrow to the offerer and close it
+    let offerer = ctx.accounts.offerer.to_account_info();
+    escrow.pay(
+        &offerer,
+        ctx.accounts.offerer_token_account.as_ref().map(|account| account.to_account_info()),
+        escrow.balance()?,
+    )?;
+    escrow.close_vault(&offerer)?;
+
+    bet.open_counter_offer_count = bet.open_counter_offer_count
+        .checked_sub(1)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+
+    // The counter-offer account will be closed automatically by Anchor's `close = offerer` constraint
+
+    Ok(())
+}
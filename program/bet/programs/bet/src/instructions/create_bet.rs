@@ -1,5 +1,11 @@
 use anchor_lang::prelude::*;
-use crate::state::bet::{Bet, BetStatus, RefereeType, BetAvailableTo};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::constants::{BET_VERSION, BPS_DENOMINATOR, MAX_PANEL_SIZE, MAX_PARLAY_LEGS, MAX_REFEREE_FEE_BPS, MIN_PARLAY_LEGS, RESOLVER_AUTHORITY_SEED};
+use crate::math::{canonical_odds, stake_at_odds};
+use crate::state::bet::{Bet, BetStatus, BetOutcome, BetTerms, RefereeType, BetAvailableTo, DisputeFallback, OddsTerms, OracleComparator, OracleTerms, RefereeFeeKind, RefereePanel, PositionSide};
+use crate::state::escrow::Escrow;
+use crate::state::parlay::{Parlay, LegResult, leg_result, read_leg, write_leg};
 use crate::state::profile::Profile;
 
 #[derive(Accounts)]
@@ -15,7 +21,7 @@ pub struct CreateBet<'info> {
     )]
     pub profile: Account<'info, Profile>,
     
-    /// CHECK: Referee account (creator for Honor System, designated for Third Party)
+    /// CHECK: Referee account (creator for Honor System, designated for Third Party, price feed for Oracle, resolver program for Smart Contract)
     pub referee: AccountInfo<'info>,
     
     #[account(
@@ -27,16 +33,46 @@ pub struct CreateBet<'info> {
     )]
     pub bet: Account<'info, Bet>,
     
-    /// CHECK: Treasury PDA for holding bet funds (will be created on first transfer)
+    // Treasury holding the bet's funds - its rent is paid by the creator and returned when the bet is deleted
     #[account(
-        mut,
+        init,
+        payer = creator,
+        space = Escrow::LEN,
         seeds = [b"bet-treasury-", bet.key().as_ref()],
         bump
     )]
-    /// CHECK: Treasury account
-    pub treasury: UncheckedAccount<'info>,
+    pub treasury: Account<'info, Escrow>,
     
     pub system_program: Program<'info, System>,
+    
+    // SPL bets only - leave these out to bet in SOL (token accounts are boxed to keep them off the stack)
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(mut)]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    
+    // Parlay bets only - holds the legs, which are passed (writable, in order) as remaining accounts
+    #[account(
+        init,
+        payer = creator,
+        space = Parlay::LEN,
+        seeds = [b"parlay", bet.key().as_ref()],
+        bump
+    )]
+    pub parlay: Option<Account<'info, Parlay>>,
 }
 
 pub fn create_bet(
@@ -45,44 +81,270 @@ pub fn create_bet(
     description: [u8; 128],
     referee_type: u8,
     category: u8,
-    odds_win: u64,
-    odds_lose: u64,
-    expires_at: i64,
-    bet_available_to: u8,
-    private_bet_recipient: Option<Pubkey>,
+    odds: OddsTerms,
+    terms: BetTerms,
 ) -> Result<()> {
+    let BetTerms {
+        expires_at,
+        resolve_by,
+        bet_available_to,
+        private_bet_recipient,
+        dispute_fallback,
+        arbiter,
+        challenge_window,
+        oracle_terms,
+        resolver_state,
+        referee_fee_kind,
+        referee_fee,
+        referee_panel,
+        partial_fills,
+        parlay_legs,
+    } = terms;
     let bet = &mut ctx.accounts.bet;
     let profile = &mut ctx.accounts.profile;
     let clock = Clock::get()?;
     
-    // Validate referee type - only allow Honor System (0) or Third Party (2)
+    // Odds are stored as odds_win:odds_lose whatever format they were quoted in,
+    // and have to leave the acceptor something to stake
+    let (odds_win, odds_lose) = canonical_odds(&odds)?;
+    stake_at_odds(bet_amount, odds_win, odds_lose)?;
+    
+    // Validate referee type - Honor System (0), Oracle (1), Third Party (2), Smart Contract (3), Mutual Consent (4), Panel (5) or Parlay (6)
     require!(
-        referee_type == RefereeType::HonorSystem as u8 || referee_type == RefereeType::ThirdParty as u8,
+        referee_type == RefereeType::HonorSystem as u8
+            || referee_type == RefereeType::Oracle as u8
+            || referee_type == RefereeType::ThirdParty as u8
+            || referee_type == RefereeType::SmartContract as u8
+            || referee_type == RefereeType::MutualConsent as u8
+            || referee_type == RefereeType::Panel as u8
+            || referee_type == RefereeType::Parlay as u8,
         crate::error::BetError::InvalidRefereeType
     );
     
+    // Validate dispute fallback - escalating to an arbiter needs an arbiter who isn't the creator
+    require!(
+        dispute_fallback == DisputeFallback::Refund as u8 || dispute_fallback == DisputeFallback::Arbiter as u8,
+        crate::error::BetError::InvalidDisputeFallback
+    );
+    if dispute_fallback == DisputeFallback::Arbiter as u8 {
+        require!(
+            arbiter.is_some() && arbiter != Some(ctx.accounts.creator.key()),
+            crate::error::BetError::InvalidArbiter
+        );
+    }
+    
+    // Referee must have time to resolve after the bet can no longer be accepted
+    require!(
+        resolve_by >= expires_at,
+        crate::error::BetError::InvalidResolutionDeadline
+    );
+    
+    // Disputes raised during the challenge window are escalated to the arbiter
+    require!(
+        challenge_window == 0
+            || (challenge_window > 0 && dispute_fallback == DisputeFallback::Arbiter as u8),
+        crate::error::BetError::InvalidChallengeWindow
+    );
+    
+    // Oracle bets settle against the feed named in their terms, the referee account is a current update for it
+    let oracle = if referee_type == RefereeType::Oracle as u8 {
+        let terms = oracle_terms.ok_or(crate::error::BetError::InvalidOracleTerms)?;
+        require!(
+            terms.comparator == OracleComparator::Above as u8 || terms.comparator == OracleComparator::Below as u8,
+            crate::error::BetError::InvalidOracleTerms
+        );
+        require!(
+            terms.max_staleness > 0 && terms.max_confidence_bps as u64 <= BPS_DENOMINATOR,
+            crate::error::BetError::InvalidOracleTerms
+        );
+        // Price must be observed after the bet stops being acceptable, and before the refund deadline
+        require!(
+            terms.observation_time >= expires_at && terms.observation_time < resolve_by,
+            crate::error::BetError::InvalidOracleTerms
+        );
+        // Read the feed once so a bet can't be made against a feed no adapter understands
+        crate::oracle::read_price(&terms, &ctx.accounts.referee)?;
+        terms
+    } else {
+        require!(
+            oracle_terms.is_none(),
+            crate::error::BetError::InvalidOracleTerms
+        );
+        OracleTerms::default()
+    };
+    
+    // Smart Contract bets are settled by the resolver program passed as the referee account
+    let (resolver_program, resolver_state) = if referee_type == RefereeType::SmartContract as u8 {
+        require!(
+            ctx.accounts.referee.executable && resolver_state.is_some(),
+            crate::error::BetError::InvalidResolverProgram
+        );
+        (Some(ctx.accounts.referee.key()), resolver_state)
+    } else {
+        require!(
+            resolver_state.is_none(),
+            crate::error::BetError::InvalidResolverProgram
+        );
+        (None, None)
+    };
+    
+    // Panel bets settle as soon as M of the N referees vote for the same outcome
+    let panel = if referee_type == RefereeType::Panel as u8 {
+        let terms = referee_panel.ok_or(crate::error::BetError::InvalidRefereePanel)?;
+        let size = terms.members.len();
+        require!(
+            size > 0 && size <= MAX_PANEL_SIZE,
+            crate::error::BetError::InvalidRefereePanel
+        );
+        // A majority threshold means two different outcomes can never both reach it
+        require!(
+            terms.threshold as usize <= size && terms.threshold as usize * 2 > size,
+            crate::error::BetError::InvalidRefereePanel
+        );
+        for (i, member) in terms.members.iter().enumerate() {
+            require!(
+                *member != ctx.accounts.creator.key() && !terms.members[..i].contains(member),
+                crate::error::BetError::InvalidRefereePanel
+            );
+        }
+        // The quorum is final, there is no outcome to challenge
+        require!(
+            challenge_window == 0,
+            crate::error::BetError::InvalidChallengeWindow
+        );
+        
+        let mut panel = RefereePanel {
+            size: size as u8,
+            threshold: terms.threshold,
+            ..RefereePanel::default()
+        };
+        panel.members[..size].copy_from_slice(&terms.members);
+        panel
+    } else {
+        require!(
+            referee_panel.is_none(),
+            crate::error::BetError::InvalidRefereePanel
+        );
+        RefereePanel::default()
+    };
+    
+    // Parlay bets are settled from their legs by settle_parlay. Each leg bet is pinned so it
+    // can't be deleted (and its result lost) before the parlay is, which is why only the
+    // creator's own bets can be legs - nobody else can keep a bet from being deleted
+    let parlay_legs = if referee_type == RefereeType::Parlay as u8 {
+        let legs = parlay_legs.ok_or(crate::error::BetError::InvalidParlay)?;
+        require!(
+            legs.len() >= MIN_PARLAY_LEGS
+                && legs.len() <= MAX_PARLAY_LEGS
+                && ctx.remaining_accounts.len() == legs.len()
+                && ctx.accounts.parlay.is_some(),
+            crate::error::BetError::InvalidParlay
+        );
+        // The creator is the long shot: at least even money, and no longer than the
+        // fair odds if every leg were a coin flip (2^legs - 1 : 1)
+        let max_odds_win = odds_lose
+            .checked_mul((1u64 << legs.len()) - 1)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+        require!(
+            odds_lose > 0 && odds_win >= odds_lose && odds_win <= max_odds_win,
+            crate::error::BetError::InvalidParlayOdds
+        );
+        // The legs decide the outcome, there is nothing to challenge
+        require!(
+            challenge_window == 0,
+            crate::error::BetError::InvalidChallengeWindow
+        );
+        
+        for (i, (leg, account)) in legs.iter().zip(ctx.remaining_accounts.iter()).enumerate() {
+            require!(
+                account.key() == leg.bet && !legs[..i].iter().any(|other| other.bet == leg.bet),
+                crate::error::BetError::InvalidParlay
+            );
+            require!(
+                leg.side == PositionSide::Creator as u8 || leg.side == PositionSide::Acceptor as u8,
+                crate::error::BetError::InvalidParlay
+            );
+            let mut leg_bet = read_leg(account)?;
+            require!(
+                leg_bet.creator == ctx.accounts.creator.key(),
+                crate::error::BetError::ParlayLegNotOwned
+            );
+            // Leg must still be undecided, and pool bets have no single winner to read
+            require!(
+                leg_result(&leg_bet, leg.side) == LegResult::Pending && !leg_bet.partial_fills,
+                crate::error::BetError::InvalidParlay
+            );
+            leg_bet.linked_parlay_count = leg_bet.linked_parlay_count
+                .checked_add(1)
+                .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+            write_leg(account, &leg_bet)?;
+        }
+        Some(legs)
+    } else {
+        require!(
+            parlay_legs.is_none() && ctx.accounts.parlay.is_none(),
+            crate::error::BetError::InvalidParlay
+        );
+        None
+    };
+    
+    // Only third-party referees can be paid, they agree to the fee in accept_referee_role
+    require!(
+        referee_fee == 0 || referee_type == RefereeType::ThirdParty as u8,
+        crate::error::BetError::InvalidRefereeFee
+    );
+    require!(
+        referee_fee_kind == RefereeFeeKind::Flat as u8 || referee_fee_kind == RefereeFeeKind::Bps as u8,
+        crate::error::BetError::InvalidRefereeFee
+    );
+    if referee_fee_kind == RefereeFeeKind::Bps as u8 {
+        require!(
+            referee_fee <= MAX_REFEREE_FEE_BPS,
+            crate::error::BetError::InvalidRefereeFee
+        );
+    }
+    
+    // Pool bets are resolved by a single referee straight away, then each fill is paid out with claim_fill
+    if partial_fills {
+        require!(
+            referee_type == RefereeType::HonorSystem as u8
+                || referee_type == RefereeType::ThirdParty as u8
+                || referee_type == RefereeType::SmartContract as u8,
+            crate::error::BetError::InvalidRefereeType
+        );
+        require!(
+            challenge_window == 0,
+            crate::error::BetError::InvalidChallengeWindow
+        );
+        require!(
+            referee_fee == 0,
+            crate::error::BetError::InvalidRefereeFee
+        );
+    }
+    
     // Set referee based on type
     let referee_pubkey = if referee_type == RefereeType::HonorSystem as u8 {
         // For Honor System, referee is the creator
         ctx.accounts.creator.key()
+    } else if referee_type == RefereeType::MutualConsent as u8
+        || referee_type == RefereeType::Panel as u8
+        || referee_type == RefereeType::Parlay as u8
+    {
+        // For Mutual Consent, Panel and Parlay, there is no single referee - the parties or the panel
+        // vote on the outcome, or the legs decide it
+        Pubkey::default()
+    } else if referee_type == RefereeType::Oracle as u8 {
+        // For Oracle, referee is the feed id - any verified update for that feed can settle the bet
+        Pubkey::new_from_array(oracle.feed_id)
+    } else if let (Some(program_id), Some(state)) = (resolver_program, resolver_state) {
+        // For Smart Contract, referee is a PDA of the resolver program - only a CPI signed
+        // by that program can produce this signer for resolve_bet
+        Pubkey::find_program_address(&[RESOLVER_AUTHORITY_SEED, state.as_ref()], &program_id).0
     } else {
         // For Third Party, referee is the designated referee account
         ctx.accounts.referee.key()
     };
     
-    // Log all input values
-    msg!("=== CREATE BET DEBUG LOG ===");
-    msg!("bet_amount: {}", bet_amount);
-    msg!("referee_type: {}", referee_type);
-    msg!("referee: {}", referee_pubkey);
-    msg!("odds_win: {}", odds_win);
-    msg!("odds_lose: {}", odds_lose);
-    msg!("expires_at: {}", expires_at);
-    msg!("current_timestamp: {}", clock.unix_timestamp);
-    msg!("creator: {}", ctx.accounts.creator.key());
-    msg!("profile.total_my_bet_count: {}", profile.total_my_bet_count);
-    msg!("=== END DEBUG LOG ===");
-    
     bet.referee = referee_pubkey;
     bet.creator = ctx.accounts.creator.key();
     bet.acceptor = None;
@@ -95,34 +357,103 @@ pub fn create_bet(
     bet.odds_win = odds_win;
     bet.odds_lose = odds_lose;
     bet.expires_at = expires_at;
-    bet.status = BetStatus::Open as u8;
+    bet.resolve_by = resolve_by;
+    // Third-party bets can't be accepted until the named referee agrees to judge them
+    bet.status = if referee_type == RefereeType::ThirdParty as u8 {
+        BetStatus::PendingReferee as u8
+    } else {
+        BetStatus::Open as u8
+    };
     bet.winner = None;
+    bet.outcome = BetOutcome::Pending as u8;
     bet.created_at = clock.unix_timestamp;
     bet.accepted_at = None;
     bet.resolved_at = None;
     bet.bet_available_to = bet_available_to;
     bet.private_bet_recipient = private_bet_recipient;
-    bet.version = 1;
+    bet.creator_proposal = BetOutcome::Pending as u8;
+    bet.acceptor_proposal = BetOutcome::Pending as u8;
+    bet.dispute_fallback = dispute_fallback;
+    bet.arbiter = if dispute_fallback == DisputeFallback::Arbiter as u8 { arbiter } else { None };
+    bet.challenge_window = challenge_window;
+    bet.challenge_deadline = None;
+    bet.disputed_by = None;
+    bet.dispute_bond = 0;
+    bet.oracle = oracle;
+    bet.resolver_program = resolver_program;
+    bet.resolver_state = resolver_state;
+    bet.mint = ctx.accounts.mint.as_ref().map(|mint| mint.key());
+    bet.protocol_fee_bps = 0;
+    bet.protocol_fee = 0;
+    bet.referee_fee_kind = referee_fee_kind;
+    bet.referee_fee = referee_fee;
+    bet.referee_fee_paid = 0;
+    bet.referee_accepted_at = None;
+    bet.panel = panel;
+    bet.terms_revision = 0;
+    bet.counter_offer_count = 0;
+    bet.open_counter_offer_count = 0;
+    bet.settlement_proposer = None;
+    bet.settlement_creator_amount = 0;
+    bet.creator_position_holder = ctx.accounts.creator.key();
+    bet.acceptor_position_holder = None;
+    bet.partial_fills = partial_fills;
+    bet.filled_amount = 0;
+    bet.filled_acceptor_amount = 0;
+    bet.fill_count = 0;
+    bet.open_fill_count = 0;
+    bet.linked_parlay_count = 0;
+    bet.creator_deposit = bet_amount; // Deposited below
+    bet.acceptor_deposit = 0;
+    bet.version = BET_VERSION;
     bet.bump = ctx.bumps.bet;
-    bet._padding = [0; 5];
-    
-    // Transfer creator's bet amount to treasury using system program
-    anchor_lang::solana_program::program::invoke(
-        &anchor_lang::solana_program::system_instruction::transfer(
-            ctx.accounts.creator.key,
-            ctx.accounts.treasury.key,
-            bet_amount,
-        ),
-        &[
-            ctx.accounts.creator.to_account_info(),
-            ctx.accounts.treasury.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
+    bet._padding = [0; 4];
+    bet._v2_padding = [0; 1];
+    
+    if let (Some(legs), Some(parlay)) = (parlay_legs, ctx.accounts.parlay.as_mut()) {
+        parlay.bet = bet.key();
+        parlay.legs[..legs.len()].copy_from_slice(&legs);
+        parlay.leg_count = legs.len() as u8;
+        parlay.version = 1;
+        parlay.bump = ctx.bumps.parlay.ok_or(crate::error::BetError::InvalidParlay)?;
+        parlay._padding = [0; 5];
+    }
+    
+    // A flat referee fee can't be more than the pot it is paid from
+    require!(
+        bet.referee_fee_amount()? <= bet.pot()?,
+        crate::error::BetError::InvalidRefereeFee
+    );
+    
+    let escrow = &mut ctx.accounts.treasury;
+    escrow.parent = ctx.accounts.bet.key();
+    escrow.version = 1;
+    escrow.bump = ctx.bumps.treasury;
+    escrow._padding = [0; 6];
+    
+    // Transfer creator's bet amount to treasury (lamports, or tokens into the vault for SPL bets)
+    let treasury = crate::treasury::BetTreasury::new(
+        &ctx.accounts.bet,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+    treasury.deposit(
+        &ctx.accounts.creator.to_account_info(),
+        ctx.accounts.creator_token_account.as_ref().map(|account| account.to_account_info()),
+        bet_amount,
     )?;
     
     // Increment creator's bet count after using it
     profile.total_my_bet_count += 1;
     
+    // The stake stays locked until the bet is cancelled, expires or pays out
+    profile.open_bet_count += 1;
+    profile.lock_stake(PositionSide::Creator, bet_amount)?;
+    
     Ok(())
 }
 
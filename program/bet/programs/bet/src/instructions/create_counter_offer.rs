@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::math::{canonical_odds, stake_at_odds};
+use crate::state::bet::{Bet, BetStatus, BetAvailableTo, OddsTerms, RefereeType};
+use crate::state::counter_offer::CounterOffer;
+use crate::state::profile::Profile;
+use crate::treasury::BetTreasury;
+
+#[derive(Accounts)]
+pub struct CreateCounterOffer<'info> {
+    #[account(mut)]
+    pub offerer: Signer<'info>,
+
+    #[account(
+        seeds = [b"username-", offerer_profile.name.as_ref()],
+        bump = offerer_profile.bump,
+        constraint = offerer_profile.wallet == offerer.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub offerer_profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        constraint = bet.creator != offerer.key() @ crate::error::BetError::CannotAcceptOwnBet,
+        constraint = !bet.partial_fills @ crate::error::BetError::PoolBet,
+        constraint = bet.panel.member_index(&offerer.key()).is_none() @ crate::error::BetError::Unauthorized,
+        constraint = bet.status != BetStatus::PendingReferee as u8 @ crate::error::BetError::RefereeNotAccepted,
+        constraint = bet.status == BetStatus::Open as u8 @ crate::error::BetError::InvalidBetStatus
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(
+        init,
+        payer = offerer,
+        space = CounterOffer::LEN,
+        seeds = [b"counter-offer", bet.key().as_ref(), &bet.counter_offer_count.to_le_bytes()],
+        bump
+    )]
+    pub counter_offer: Account<'info, CounterOffer>,
+
+    /// CHECK: Escrow PDA holding the offerer's stake (will be created on first transfer)
+    #[account(
+        mut,
+        seeds = [b"counter-offer-escrow-", counter_offer.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Escrow account
+    pub escrow: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // SPL bets only - leave these out for SOL bets
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        init,
+        payer = offerer,
+        associated_token::mint = mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub offerer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+}
+
+pub fn create_counter_offer(
+    ctx: Context<CreateCounterOffer>,
+    bet_amount: u64,
+    odds: OddsTerms,
+    expires_at: i64,
+) -> Result<()> {
+    let bet = &mut ctx.accounts.bet;
+    let clock = Clock::get()?;
+
+    let (odds_win, odds_lose) = canonical_odds(&odds)?;
+
+    // The offer can only be accepted while the bet itself can still be accepted
+    require!(
+        bet.expires_at > clock.unix_timestamp,
+        crate::error::BetError::BetExpired
+    );
+    require!(
+        expires_at > clock.unix_timestamp && expires_at <= bet.expires_at,
+        crate::error::BetError::InvalidExpiration
+    );
+
+    // Same eligibility as accept_bet - the arbiter can't be a party, and private bets
+    // only take offers from their recipient
+    require!(
+        bet.arbiter != Some(ctx.accounts.offerer.key()),
+        crate::error::BetError::InvalidArbiter
+    );
+    if bet.bet_available_to == BetAvailableTo::Private as u8 {
+        require!(
+            bet.private_bet_recipient == Some(ctx.accounts.offerer.key()),
+            crate::error::BetError::Unauthorized
+        );
+    }
+
+    // Parlay odds were checked against the legs at creation, only the stake can be countered
+    if bet.referee_type == RefereeType::Parlay as u8 {
+        require!(
+            odds_win == bet.odds_win && odds_lose == bet.odds_lose,
+            crate::error::BetError::InvalidParlayOdds
+        );
+    }
+
+    // Offerer escrows what they would stake at the proposed terms: bet_amount * (oddsWin / oddsLose)
+    let offerer_bet_amount = stake_at_odds(bet_amount, odds_win, odds_lose)?;
+
+    let counter_offer = &mut ctx.accounts.counter_offer;
+    counter_offer.bet = bet.key();
+    counter_offer.offerer = ctx.accounts.offerer.key();
+    counter_offer.bet_amount = bet_amount;
+    counter_offer.odds_win = odds_win;
+    counter_offer.odds_lose = odds_lose;
+    counter_offer.offerer_bet_amount = offerer_bet_amount;
+    counter_offer.expires_at = expires_at;
+    counter_offer.created_at = clock.unix_timestamp;
+    counter_offer.version = 1;
+    counter_offer.bump = ctx.bumps.counter_offer;
+    counter_offer.escrow_bump = ctx.bumps.escrow;
+    counter_offer._padding = [0; 5];
+
+    bet.counter_offer_count = bet.counter_offer_count
+        .checked_add(1)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+    bet.open_counter_offer_count = bet.open_counter_offer_count
+        .checked_add(1)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+
+    // Transfer the offerer's stake to the escrow (lamports, or tokens into the escrow vault for SPL bets).
+    // Escrow also holds the transfer fee for moving the stake into the bet treasury on accept, any
+    // left over is refunded to the offerer
+    let escrow = BetTreasury::for_counter_offer(
+        &ctx.accounts.bet,
+        &ctx.accounts.counter_offer,
+        ctx.accounts.escrow.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.escrow_vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+    let escrow_amount = offerer_bet_amount
+        .checked_add(escrow.transfer_fee(offerer_bet_amount)?)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+    escrow.deposit(
+        &ctx.accounts.offerer.to_account_info(),
+        ctx.accounts.offerer_token_account.as_ref().map(|account| account.to_account_info()),
+        escrow_amount,
+    )?;
+
+    Ok(())
+}
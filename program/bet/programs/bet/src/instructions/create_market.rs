@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::constants::{MAX_MARKET_OUTCOMES, RESOLVER_AUTHORITY_SEED};
+use crate::state::bet::{BetStatus, RefereeType, BetAvailableTo};
+use crate::state::escrow::Escrow;
+use crate::state::market::{Market, MarketTerms};
+use crate::state::profile::Profile;
+
+#[derive(Accounts)]
+pub struct CreateMarket<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"username-", profile.name.as_ref()],
+        bump = profile.bump,
+        constraint = profile.wallet == creator.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub profile: Account<'info, Profile>,
+
+    /// CHECK: Referee account (creator for Honor System, designated for Third Party, resolver program for Smart Contract)
+    pub referee: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = Market::LEN,
+        seeds = [b"market", creator.key().as_ref(), &profile.total_market_count.to_le_bytes()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    // Treasury holding the market's stakes - its rent is paid by the creator and returned when the market is deleted
+    #[account(
+        init,
+        payer = creator,
+        space = Escrow::LEN,
+        seeds = [b"market-treasury-", market.key().as_ref()],
+        bump
+    )]
+    pub treasury: Account<'info, Escrow>,
+
+    pub system_program: Program<'info, System>,
+
+    // SPL markets only - leave these out to stake in SOL
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+}
+
+pub fn create_market(
+    ctx: Context<CreateMarket>,
+    description: [u8; 128],
+    category: u8,
+    referee_type: u8,
+    outcome_labels: Vec<[u8; 32]>,
+    terms: MarketTerms,
+) -> Result<()> {
+    let MarketTerms {
+        expires_at,
+        resolve_by,
+        bet_available_to,
+        private_bet_recipient,
+        resolver_state,
+    } = terms;
+    let market = &mut ctx.accounts.market;
+    let profile = &mut ctx.accounts.profile;
+    let clock = Clock::get()?;
+
+    // A single referee names the winning outcome - Oracle, Mutual Consent and Panel are two-sided
+    require!(
+        referee_type == RefereeType::HonorSystem as u8
+            || referee_type == RefereeType::ThirdParty as u8
+            || referee_type == RefereeType::SmartContract as u8,
+        crate::error::BetError::InvalidRefereeType
+    );
+
+    let outcome_count = outcome_labels.len();
+    require!(
+        (2..=MAX_MARKET_OUTCOMES).contains(&outcome_count),
+        crate::error::BetError::InvalidMarketOutcomes
+    );
+
+    require!(
+        expires_at > clock.unix_timestamp,
+        crate::error::BetError::InvalidExpiration
+    );
+
+    // Referee must have time to resolve after staking closes
+    require!(
+        resolve_by >= expires_at,
+        crate::error::BetError::InvalidResolutionDeadline
+    );
+
+    // Private markets need someone to be private with
+    require!(
+        bet_available_to == BetAvailableTo::Public as u8
+            || bet_available_to == BetAvailableTo::FriendsOnly as u8
+            || (bet_available_to == BetAvailableTo::Private as u8 && private_bet_recipient.is_some()),
+        crate::error::BetError::InvalidBetAvailableTo
+    );
+
+    // Smart Contract markets are resolved by the resolver program passed as the referee account
+    let (resolver_program, resolver_state) = if referee_type == RefereeType::SmartContract as u8 {
+        require!(
+            ctx.accounts.referee.executable && resolver_state.is_some(),
+            crate::error::BetError::InvalidResolverProgram
+        );
+        (Some(ctx.accounts.referee.key()), resolver_state)
+    } else {
+        require!(
+            resolver_state.is_none(),
+            crate::error::BetError::InvalidResolverProgram
+        );
+        (None, None)
+    };
+
+    // Set referee based on type, same as create_bet
+    let referee_pubkey = if referee_type == RefereeType::HonorSystem as u8 {
+        ctx.accounts.creator.key()
+    } else if let (Some(program_id), Some(state)) = (resolver_program, resolver_state) {
+        Pubkey::find_program_address(&[RESOLVER_AUTHORITY_SEED, state.as_ref()], &program_id).0
+    } else {
+        ctx.accounts.referee.key()
+    };
+
+    let mut labels = [[0u8; 32]; MAX_MARKET_OUTCOMES];
+    labels[..outcome_count].copy_from_slice(&outcome_labels);
+
+    market.referee = referee_pubkey;
+    market.creator = ctx.accounts.creator.key();
+    market.creator_username = profile.name;
+    market.description = description;
+    market.referee_type = referee_type;
+    market.category = category;
+    market.bet_available_to = bet_available_to;
+    market.private_bet_recipient = if bet_available_to == BetAvailableTo::Private as u8 { private_bet_recipient } else { None };
+    market.outcome_count = outcome_count as u8;
+    market.outcome_labels = labels;
+    market.outcome_pools = [0; MAX_MARKET_OUTCOMES];
+    market.total_pool = 0;
+    market.expires_at = expires_at;
+    market.resolve_by = resolve_by;
+    market.status = BetStatus::Open as u8;
+    market.winning_outcome = None;
+    market.created_at = clock.unix_timestamp;
+    market.resolved_at = None;
+    market.resolver_program = resolver_program;
+    market.resolver_state = resolver_state;
+    market.mint = ctx.accounts.mint.as_ref().map(|mint| mint.key());
+    market.protocol_fee_bps = 0;
+    market.protocol_fee = 0;
+    market.stake_count = 0;
+    market.open_stake_count = 0;
+    market.version = 1;
+    market.bump = ctx.bumps.market;
+    market._padding = [0; 6];
+
+    let escrow = &mut ctx.accounts.treasury;
+    escrow.parent = market.key();
+    escrow.version = 1;
+    escrow.bump = ctx.bumps.treasury;
+    escrow._padding = [0; 6];
+
+    // Increment creator's market count after using it
+    profile.total_market_count = profile.total_market_count
+        .checked_add(1)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::constants::PROFILE_VERSION;
 use crate::state::profile::Profile;
 
 #[derive(Accounts)]
@@ -36,9 +37,28 @@ pub fn create_profile(ctx: Context<CreateProfile>, name: [u8; 32]) -> Result<()>
     profile.total_my_bet_volume = 0;
     profile.total_accepted_bet_volume = 0;
     profile.created_at = clock.unix_timestamp;
-    profile.version = 1;
+    profile.version = PROFILE_VERSION;
     profile.bump = ctx.bumps.profile;
-    profile._padding = [0; 7];
+    profile.total_market_count = 0;
+    profile._padding = [0; 3];
+    profile.total_push_count = 0;
+    profile.total_void_count = 0;
+    profile.total_settled_count = 0;
+    profile.total_staked = 0;
+    profile.total_returned = 0;
+    profile.realized_profit = 0;
+    profile.roi_bps = 0;
+    profile.biggest_win = 0;
+    profile.current_streak = 0;
+    profile.longest_win_streak = 0;
+    profile.longest_loss_streak = 0;
+    profile._stats_padding = [0; 4];
+    profile.open_bet_count = 0;
+    profile.active_bet_count = 0;
+    profile.locked_as_creator = 0;
+    profile.locked_as_acceptor = 0;
+    profile.max_exposure = None;
+    profile._exposure_padding = [0; 7];
     
     Ok(())
 }
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::bet::{Bet, BetStatus, PositionSide};
+use crate::state::profile::Profile;
+
+#[derive(Accounts)]
+pub struct DeclineRefereeRole<'info> {
+    pub referee: Signer<'info>,
+    
+    /// CHECK: Creator account to receive the refund (validated by bet.creator)
+    #[account(mut)]
+    pub creator: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"username-", creator_profile.name.as_ref()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.wallet == creator.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub creator_profile: Account<'info, Profile>,
+    
+    #[account(
+        mut,
+        constraint = bet.creator == creator.key() @ crate::error::BetError::InvalidBetCreator,
+        constraint = bet.referee == referee.key() @ crate::error::BetError::Unauthorized,
+        constraint = bet.status == BetStatus::PendingReferee as u8 @ crate::error::BetError::InvalidBetStatus
+    )]
+    pub bet: Account<'info, Bet>,
+    
+    /// CHECK: Treasury PDA for holding bet funds (must exist from create_bet)
+    #[account(
+        mut,
+        seeds = [b"bet-treasury-", bet.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Treasury account
+    pub treasury: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    // SPL bets only - leave these out for SOL bets
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(mut)]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn decline_referee_role(ctx: Context<DeclineRefereeRole>) -> Result<()> {
+    let treasury = crate::treasury::BetTreasury::new(
+        &ctx.accounts.bet,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+    
+    // Return the creator's stake - the bet never went live
+    let bet = &mut ctx.accounts.bet;
+    let creator = ctx.accounts.creator.to_account_info();
+    let creator_token_account = ctx.accounts.creator_token_account.as_ref().map(|account| account.to_account_info());
+    let refund = bet.creator_deposit;
+    bet.creator_deposit = 0;
+    treasury.pay(&creator, creator_token_account.clone(), refund)?;
+    treasury.sweep_excess(bet, &creator, creator_token_account)?;
+    
+    // Cancelled without counting against the creator's cancelled_bet_count
+    bet.status = BetStatus::Cancelled as u8;
+    
+    let creator_profile = &mut ctx.accounts.creator_profile;
+    creator_profile.open_bet_count = creator_profile.open_bet_count.saturating_sub(1);
+    creator_profile.unlock_stake(PositionSide::Creator, refund);
+    
+    Ok(())
+}
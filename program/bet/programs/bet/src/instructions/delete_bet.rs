@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
-use crate::state::bet::{Bet, BetStatus};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::bet::{Bet, BetStatus, RefereeType};
+use crate::state::parlay::{Parlay, read_leg, write_leg};
 
 #[derive(Accounts)]
 pub struct DeleteBet<'info> {
@@ -14,7 +16,10 @@ pub struct DeleteBet<'info> {
         mut,
         close = creator,  // Close the bet account and send rent to creator
         constraint = bet.creator == creator.key() @ crate::error::BetError::InvalidBetCreator,
-        constraint = bet.status == BetStatus::Cancelled as u8 || bet.status == BetStatus::Resolved as u8 @ crate::error::BetError::InvalidBetStatus
+        constraint = bet.status == BetStatus::Cancelled as u8 || bet.status == BetStatus::Resolved as u8 || bet.status == BetStatus::Expired as u8 || bet.status == BetStatus::Voided as u8 || bet.status == BetStatus::Settled as u8 @ crate::error::BetError::InvalidBetStatus,
+        constraint = bet.open_counter_offer_count == 0 @ crate::error::BetError::CounterOffersOpen,
+        constraint = bet.open_fill_count == 0 @ crate::error::BetError::FillsOpen,
+        constraint = bet.linked_parlay_count == 0 @ crate::error::BetError::LinkedToParlay
     )]
     pub bet: Account<'info, Bet>,
     
@@ -28,40 +33,71 @@ pub struct DeleteBet<'info> {
     pub treasury: UncheckedAccount<'info>,
     
     pub system_program: Program<'info, System>,
+    
+    // SPL bets only - leave these out for SOL bets (mint is mut to harvest withheld transfer fees)
+    #[account(mut)]
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(mut)]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    // Parlay bets only - closed with the bet, its legs are passed (writable, in order) as remaining accounts to unpin them
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"parlay", bet.key().as_ref()],
+        bump = parlay.bump
+    )]
+    pub parlay: Option<Account<'info, Parlay>>,
 }
 
 pub fn delete_bet(ctx: Context<DeleteBet>) -> Result<()> {
-    // Get bet key and treasury bump before mutable borrow
-    let bet_key = ctx.accounts.bet.key();
-    let treasury_bump = ctx.bumps.treasury;
+    let treasury = crate::treasury::BetTreasury::new(
+        &ctx.accounts.bet,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+    
+    // Every recorded stake and fee has been paid out by now, so anything left is excess - it goes back to the creator
+    treasury.sweep_excess(
+        &ctx.accounts.bet,
+        &ctx.accounts.creator.to_account_info(),
+        ctx.accounts.creator_token_account.as_ref().map(|account| account.to_account_info()),
+    )?;
+    
+    // Close the SPL vault, its rent goes back to the creator who paid for it at create_bet
+    treasury.close_vault(&ctx.accounts.creator.to_account_info())?;
     
-    // Get treasury balance (all SOL in treasury)
-    let treasury_balance = ctx.accounts.treasury.lamports();
+    // Then the escrow itself, returning the rent the creator paid for it
+    treasury.close(&ctx.accounts.creator.to_account_info())?;
     
-    // Transfer any remaining SOL from treasury back to creator using system program
-    // Must use invoke_signed because treasury is a PDA and needs program signature
-    if treasury_balance > 0 {
-        // Prepare seeds for PDA signing
-        let seeds = &[
-            b"bet-treasury-",
-            bet_key.as_ref(),
-            &[treasury_bump],
-        ];
-        let signer_seeds = &[&seeds[..]];
-        
-        anchor_lang::solana_program::program::invoke_signed(
-            &anchor_lang::solana_program::system_instruction::transfer(
-                ctx.accounts.treasury.key,
-                ctx.accounts.creator.key,
-                treasury_balance,
-            ),
-            &[
-                ctx.accounts.treasury.to_account_info(),
-                ctx.accounts.creator.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            signer_seeds,
-        )?;
+    // Release the parlay's legs so they can be deleted too
+    if ctx.accounts.bet.referee_type == RefereeType::Parlay as u8 {
+        let parlay = ctx.accounts.parlay.as_ref().ok_or(crate::error::BetError::InvalidParlay)?;
+        require!(
+            ctx.remaining_accounts.len() == parlay.legs().len(),
+            crate::error::BetError::InvalidParlay
+        );
+        for (leg, account) in parlay.legs().iter().zip(ctx.remaining_accounts.iter()) {
+            require!(
+                account.key() == leg.bet,
+                crate::error::BetError::InvalidParlay
+            );
+            let mut leg_bet = read_leg(account)?;
+            leg_bet.linked_parlay_count = leg_bet.linked_parlay_count
+                .checked_sub(1)
+                .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+            write_leg(account, &leg_bet)?;
+        }
     }
     
     // The bet account will be closed automatically by Anchor's `close = creator` constraint
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::bet::BetStatus;
+use crate::state::market::Market;
+use crate::treasury::BetTreasury;
+
+#[derive(Accounts)]
+pub struct DeleteMarket<'info> {
+    /// CHECK: Anyone can call this instruction (permissionless)
+    pub signer: Signer<'info>,
+
+    /// CHECK: Creator account to receive rent (validated by market.creator)
+    #[account(mut)]
+    pub creator: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        close = creator,  // Close the market account and send rent to creator
+        constraint = market.creator == creator.key() @ crate::error::BetError::InvalidBetCreator,
+        constraint = market.status == BetStatus::Resolved as u8 || market.status == BetStatus::Voided as u8 @ crate::error::BetError::InvalidBetStatus,
+        constraint = market.open_stake_count == 0 @ crate::error::BetError::StakesOpen
+    )]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: Treasury PDA holding the market's stakes
+    #[account(
+        mut,
+        seeds = [b"market-treasury-", market.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Treasury account
+    pub treasury: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // SPL markets only - leave these out for SOL markets (mint is mut to harvest withheld transfer fees)
+    #[account(mut)]
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn delete_market(ctx: Context<DeleteMarket>) -> Result<()> {
+    let treasury = BetTreasury::for_market(
+        &ctx.accounts.market,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+
+    // Every stake has been claimed, anything left is rounding dust
+    treasury.pay(
+        &ctx.accounts.creator.to_account_info(),
+        ctx.accounts.creator_token_account.as_ref().map(|account| account.to_account_info()),
+        treasury.balance()?,
+    )?;
+
+    // Close the SPL vault, its rent goes back to the creator who paid for it at create_market
+    treasury.close_vault(&ctx.accounts.creator.to_account_info())?;
+
+    // Then the escrow itself, returning the rent the creator paid for it
+    treasury.close(&ctx.accounts.creator.to_account_info())?;
+
+    // The market account will be closed automatically by Anchor's `close = creator` constraint
+
+    Ok(())
+}
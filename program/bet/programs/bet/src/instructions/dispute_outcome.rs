@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::constants::DISPUTE_BOND_BPS;
+use crate::math::bps_of;
+use crate::state::bet::{Bet, BetStatus, BetOutcome};
+
+#[derive(Accounts)]
+pub struct DisputeOutcome<'info> {
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bet.status == BetStatus::PendingFinalization as u8 @ crate::error::BetError::InvalidBetStatus
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// CHECK: Treasury PDA for holding bet funds (must exist from create_bet)
+    #[account(
+        mut,
+        seeds = [b"bet-treasury-", bet.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Treasury account
+    pub treasury: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // SPL bets only - leave these out for SOL bets
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub disputer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn dispute_outcome(ctx: Context<DisputeOutcome>) -> Result<()> {
+    let treasury = crate::treasury::BetTreasury::new(
+        &ctx.accounts.bet,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+
+    let bet = &mut ctx.accounts.bet;
+    let disputer = ctx.accounts.disputer.key();
+    let clock = Clock::get()?;
+
+    // Disputes are only accepted while the challenge window is open
+    let challenge_deadline = bet.challenge_deadline
+        .ok_or(crate::error::BetError::InvalidBetStatus)?;
+    require!(
+        clock.unix_timestamp < challenge_deadline,
+        crate::error::BetError::ChallengeWindowClosed
+    );
+
+    // Only the side that loses under the pending outcome can dispute it (either side for Push/Void),
+    // through whoever holds that side's position now
+    let pending_outcome = BetOutcome::try_from(bet.outcome)?;
+    let is_creator = disputer == bet.creator_position_holder;
+    let is_acceptor = Some(disputer) == bet.acceptor_position_holder;
+    let can_dispute = match pending_outcome {
+        BetOutcome::CreatorWins => is_acceptor,
+        BetOutcome::AcceptorWins => is_creator,
+        BetOutcome::Push | BetOutcome::Void => is_creator || is_acceptor,
+        BetOutcome::Pending => false,
+    };
+    require!(can_dispute, crate::error::BetError::Unauthorized);
+
+    // Bond is a share of the whole pot so disputing isn't free
+    let dispute_bond = bps_of(bet.pot()?, DISPUTE_BOND_BPS)?;
+
+    // Transfer the bond to treasury (in the bet's mint for SPL bets), it is paid out when the arbiter rules
+    treasury.deposit(
+        &ctx.accounts.disputer.to_account_info(),
+        ctx.accounts.disputer_token_account.as_ref().map(|account| account.to_account_info()),
+        dispute_bond,
+    )?;
+
+    bet.status = BetStatus::Disputed as u8;
+    // Recorded by side (its original party) so the bond follows the position if it changes hands again
+    let disputed_as_creator = match pending_outcome {
+        BetOutcome::AcceptorWins => true,
+        BetOutcome::CreatorWins => false,
+        _ => is_creator,
+    };
+    bet.disputed_by = if disputed_as_creator { Some(bet.creator) } else { bet.acceptor };
+    bet.dispute_bond = dispute_bond;
+    bet.extend_for_arbitration(clock.unix_timestamp)?;
+
+    Ok(())
+}
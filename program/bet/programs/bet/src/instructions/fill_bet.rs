@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::math::stake_at_odds;
+use crate::state::bet::{Bet, BetStatus, BetAvailableTo, PositionSide};
+use crate::state::fill::Fill;
+use crate::state::profile::Profile;
+
+#[derive(Accounts)]
+pub struct FillBet<'info> {
+    #[account(mut)]
+    pub acceptor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"username-", acceptor_profile.name.as_ref()],
+        bump = acceptor_profile.bump,
+        constraint = acceptor_profile.wallet == acceptor.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub acceptor_profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        constraint = bet.partial_fills @ crate::error::BetError::NotPoolBet,
+        constraint = bet.creator != acceptor.key() @ crate::error::BetError::CannotAcceptOwnBet,
+        constraint = bet.status != BetStatus::PendingReferee as u8 @ crate::error::BetError::RefereeNotAccepted,
+        constraint = bet.status == BetStatus::Open as u8 @ crate::error::BetError::InvalidBetStatus
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(
+        mut,
+        seeds = [b"username-", creator_profile.name.as_ref()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.wallet == bet.creator @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub creator_profile: Account<'info, Profile>,
+
+    #[account(
+        init,
+        payer = acceptor,
+        space = Fill::LEN,
+        seeds = [b"fill", bet.key().as_ref(), &bet.fill_count.to_le_bytes()],
+        bump
+    )]
+    pub fill: Account<'info, Fill>,
+
+    /// CHECK: Treasury PDA for holding bet funds (must exist from create_bet)
+    #[account(
+        mut,
+        seeds = [b"bet-treasury-", bet.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Treasury account
+    pub treasury: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // SPL bets only - leave these out for SOL bets
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub acceptor_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+// Take `creator_amount` of an open pool bet's stake at the posted odds. The pool closes to
+// new fills once the whole stake is matched, or at expiry through reclaim_expired_bet.
+pub fn fill_bet(
+    ctx: Context<FillBet>,
+    creator_amount: u64,
+    expected_terms_revision: u32,
+) -> Result<()> {
+    let treasury = crate::treasury::BetTreasury::new(
+        &ctx.accounts.bet,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+
+    let bet = &mut ctx.accounts.bet;
+    let acceptor = ctx.accounts.acceptor.key();
+    let clock = Clock::get()?;
+
+    require!(
+        bet.expires_at > clock.unix_timestamp,
+        crate::error::BetError::BetExpired
+    );
+
+    // Same eligibility as accept_bet
+    require!(
+        bet.arbiter != Some(acceptor),
+        crate::error::BetError::InvalidArbiter
+    );
+    if bet.bet_available_to == BetAvailableTo::Private as u8 {
+        require!(
+            bet.private_bet_recipient == Some(acceptor),
+            crate::error::BetError::Unauthorized
+        );
+    }
+
+    // Odds must be the ones the acceptor was shown
+    require!(
+        bet.terms_revision == expected_terms_revision,
+        crate::error::BetError::TermsChanged
+    );
+
+    let unfilled_amount = bet.bet_amount
+        .checked_sub(bet.filled_amount)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+    require!(
+        creator_amount > 0 && creator_amount <= unfilled_amount,
+        crate::error::BetError::InvalidFillAmount
+    );
+
+    // Acceptor's stake for this slice: creator_amount * (oddsWin / oddsLose)
+    let acceptor_amount = stake_at_odds(creator_amount, bet.odds_win, bet.odds_lose)?;
+
+    treasury.deposit(
+        &ctx.accounts.acceptor.to_account_info(),
+        ctx.accounts.acceptor_token_account.as_ref().map(|account| account.to_account_info()),
+        acceptor_amount,
+    )?;
+
+    let fill = &mut ctx.accounts.fill;
+    fill.bet = bet.key();
+    fill.acceptor = acceptor;
+    fill.creator_amount = creator_amount;
+    fill.acceptor_amount = acceptor_amount;
+    fill.created_at = clock.unix_timestamp;
+    fill.version = 1;
+    fill.bump = ctx.bumps.fill;
+    fill._padding = [0; 6];
+
+    bet.filled_amount = bet.filled_amount
+        .checked_add(creator_amount)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+    bet.filled_acceptor_amount = bet.filled_acceptor_amount
+        .checked_add(acceptor_amount)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+    bet.acceptor_deposit = bet.acceptor_deposit
+        .checked_add(acceptor_amount)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+    bet.fill_count = bet.fill_count
+        .checked_add(1)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+    bet.open_fill_count = bet.open_fill_count
+        .checked_add(1)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+
+    // Fully matched - the pool is now waiting on the referee like an accepted bet
+    if bet.filled_amount == bet.bet_amount {
+        bet.status = BetStatus::Accepted as u8;
+        bet.accepted_at = Some(clock.unix_timestamp);
+
+        let creator_profile = &mut ctx.accounts.creator_profile;
+        creator_profile.open_bet_count = creator_profile.open_bet_count.saturating_sub(1);
+        creator_profile.active_bet_count += 1;
+    }
+
+    // Each fill is its own position, locked until claim_fill pays it out
+    let acceptor_profile = &mut ctx.accounts.acceptor_profile;
+    acceptor_profile.total_bets_accepted_count += 1;
+    acceptor_profile.active_bet_count += 1;
+    acceptor_profile.lock_stake(PositionSide::Acceptor, acceptor_amount)?;
+
+    Ok(())
+}
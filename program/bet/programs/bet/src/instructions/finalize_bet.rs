@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::instructions::resolve_bet::Settlement;
+use crate::treasury::BetTreasury;
+use crate::state::bet::{Bet, BetStatus, BetOutcome};
+use crate::state::config::Config;
+use crate::state::profile::Profile;
+
+#[derive(Accounts)]
+pub struct FinalizeBet<'info> {
+    /// CHECK: Anyone can call this instruction (permissionless)
+    pub caller: Signer<'info>,
+
+    /// CHECK: Creator is validated by checking bet.creator matches this account (mut for SOL transfer)
+    #[account(mut)]
+    pub creator: AccountInfo<'info>,
+
+    /// CHECK: Acceptor is validated by checking bet.acceptor matches this account (mut for SOL transfer)
+    #[account(mut)]
+    pub acceptor: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"username-", creator_profile.name.as_ref()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.wallet == creator.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub creator_profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        seeds = [b"username-", acceptor_profile.name.as_ref()],
+        bump = acceptor_profile.bump,
+        constraint = acceptor_profile.wallet == acceptor.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub acceptor_profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        constraint = bet.creator == creator.key() @ crate::error::BetError::InvalidBetCreator,
+        constraint = bet.status == BetStatus::PendingFinalization as u8 @ crate::error::BetError::InvalidBetStatus,
+        constraint = bet.acceptor == Some(acceptor.key()) @ crate::error::BetError::BetNotAccepted
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// CHECK: Treasury PDA for holding bet funds (must exist from create_bet)
+    #[account(
+        mut,
+        seeds = [b"bet-treasury-", bet.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Treasury account
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Fee vault PDA receiving the protocol fee
+    #[account(
+        mut,
+        seeds = [b"fee-vault"],
+        bump = config.fee_vault_bump
+    )]
+    pub fee_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Referee receiving the referee fee, validated against bet.referee (only needed for paid referees)
+    #[account(mut)]
+    pub referee: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Current holder of the creator's position, validated against bet.creator_position_holder (only needed once it has been transferred)
+    #[account(mut)]
+    pub creator_position_holder: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Current holder of the acceptor's position, validated against bet.acceptor_position_holder (only needed once it has been transferred)
+    #[account(mut)]
+    pub acceptor_position_holder: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+
+    // SPL bets only - leave these out for SOL bets
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub acceptor_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub fee_vault_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub referee_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn finalize_bet(ctx: Context<FinalizeBet>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Pending outcome only pays out once the challenge window has closed undisputed
+    let challenge_deadline = ctx.accounts.bet.challenge_deadline
+        .ok_or(crate::error::BetError::InvalidBetStatus)?;
+    require!(
+        clock.unix_timestamp >= challenge_deadline,
+        crate::error::BetError::ChallengeWindowOpen
+    );
+
+    let outcome = BetOutcome::try_from(ctx.accounts.bet.outcome)?;
+
+    let treasury = BetTreasury::new(
+        &ctx.accounts.bet,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+
+    Settlement {
+        creator: ctx.accounts.creator.to_account_info(),
+        acceptor: ctx.accounts.acceptor.to_account_info(),
+        creator_position_holder: ctx.accounts.creator_position_holder.as_ref().map(|account| account.to_account_info()),
+        acceptor_position_holder: ctx.accounts.acceptor_position_holder.as_ref().map(|account| account.to_account_info()),
+        creator_profile: &mut ctx.accounts.creator_profile,
+        acceptor_profile: &mut ctx.accounts.acceptor_profile,
+        bet: &mut ctx.accounts.bet,
+        creator_token_account: ctx.accounts.creator_token_account.as_ref().map(|account| account.to_account_info()),
+        acceptor_token_account: ctx.accounts.acceptor_token_account.as_ref().map(|account| account.to_account_info()),
+        treasury,
+        fee_bps: ctx.accounts.config.fee_bps,
+        fee_vault: ctx.accounts.fee_vault.to_account_info(),
+        fee_vault_token_account: ctx.accounts.fee_vault_token_account.as_ref().map(|account| account.to_account_info()),
+        referee: ctx.accounts.referee.as_ref().map(|account| account.to_account_info()),
+        referee_token_account: ctx.accounts.referee_token_account.as_ref().map(|account| account.to_account_info()),
+    }
+    .settle(outcome)
+}
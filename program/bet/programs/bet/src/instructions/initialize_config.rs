@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_PROTOCOL_FEE_BPS;
+use crate::state::config::Config;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = Config::LEN,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// CHECK: Fee vault PDA holding protocol fees (funded to rent exemption here)
+    #[account(
+        mut,
+        seeds = [b"fee-vault"],
+        bump
+    )]
+    pub fee_vault: UncheckedAccount<'info>,
+    
+    // Only the program's upgrade authority can initialize the config
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ crate::error::BetError::Unauthorized
+    )]
+    pub program: Program<'info, crate::program::Bet>,
+    
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ crate::error::BetError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_config(
+    ctx: Context<InitializeConfig>,
+    fee_bps: u16,
+    fee_recipient: Pubkey,
+) -> Result<()> {
+    require!(
+        fee_bps <= MAX_PROTOCOL_FEE_BPS,
+        crate::error::BetError::InvalidProtocolFee
+    );
+    
+    let config = &mut ctx.accounts.config;
+    config.authority = ctx.accounts.authority.key();
+    config.fee_recipient = fee_recipient;
+    config.fee_bps = fee_bps;
+    config.version = 1;
+    config.bump = ctx.bumps.config;
+    config.fee_vault_bump = ctx.bumps.fee_vault;
+    config._padding = [0; 3];
+    
+    // Fund the fee vault to rent exemption so small fees can be paid into it
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+    let top_up = rent_exempt_minimum.saturating_sub(ctx.accounts.fee_vault.lamports());
+    if top_up > 0 {
+        anchor_lang::solana_program::program::invoke(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                ctx.accounts.authority.key,
+                ctx.accounts.fee_vault.key,
+                top_up,
+            ),
+            &[
+                ctx.accounts.authority.to_account_info(),
+                ctx.accounts.fee_vault.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+    
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::constants::{BET_VERSION, MIGRATED_BET_RESOLUTION_PERIOD};
+use crate::state::bet::{Bet, BetOutcome, BetStatus, OracleTerms, RefereePanel};
+
+#[derive(Accounts)]
+pub struct MigrateBet<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: A bet written by an older version of the program is too small to load as the
+    /// current Bet, so its discriminator is checked by hand in migrate_bet
+    #[account(
+        mut,
+        owner = crate::ID
+    )]
+    pub bet: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Bring a bet up to BET_VERSION, growing it to the current layout with the payer covering the
+// extra rent. Older bets can't be loaded by any other instruction until this has run, so anyone
+// can run it - a bet migrates the same way whoever pays. Does nothing for a bet that is already current.
+//
+// The bet keeps the treasury it was created with: a version 1 treasury is a plain system account,
+// which BetTreasury still pays out of with a signed transfer.
+pub fn migrate_bet(ctx: Context<MigrateBet>) -> Result<()> {
+    let bet_info = ctx.accounts.bet.to_account_info();
+
+    {
+        let data = bet_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == *Bet::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+    }
+
+    if bet_info.data_len() < Bet::LEN {
+        let rent_due = Rent::get()?
+            .minimum_balance(Bet::LEN)
+            .saturating_sub(bet_info.lamports());
+        if rent_due > 0 {
+            anchor_lang::solana_program::program::invoke(
+                &anchor_lang::solana_program::system_instruction::transfer(
+                    ctx.accounts.payer.key,
+                    bet_info.key,
+                    rent_due,
+                ),
+                &[
+                    ctx.accounts.payer.to_account_info(),
+                    bet_info.clone(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+        }
+        bet_info.resize(Bet::LEN)?;
+    }
+
+    // Version 2 only appended fields or took over padding that was always written as zero, and
+    // resize zero-extends, so an older bet reads as the current layout with the newer fields
+    // zeroed. The steps below spell out what each version starts from
+    let mut bet = Bet::try_deserialize(&mut &bet_info.try_borrow_data()?[..])?;
+    if bet.version >= BET_VERSION {
+        return Ok(());
+    }
+
+    // Version 2: outcomes, deadlines, disputes, fees, positions and stake bookkeeping. Version 1
+    // bets were SOL bets between two parties, refereed by the creator or a third party, and the
+    // treasury held the creator's stake while open and both stakes once accepted
+    if bet.version < 2 {
+        bet.outcome = match (bet.status, bet.winner) {
+            (status, Some(winner)) if status == BetStatus::Resolved as u8 && winner == bet.creator => {
+                BetOutcome::CreatorWins as u8
+            }
+            (status, Some(winner)) if status == BetStatus::Resolved as u8 && Some(winner) == bet.acceptor => {
+                BetOutcome::AcceptorWins as u8
+            }
+            _ => BetOutcome::Pending as u8,
+        };
+        bet._padding = [0; 4];
+
+        // The referee never had a deadline, so give them one from now at the earliest
+        let now = Clock::get()?.unix_timestamp;
+        bet.resolve_by = bet.expires_at
+            .max(now)
+            .checked_add(MIGRATED_BET_RESOLUTION_PERIOD)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+
+        bet.creator_proposal = 0;
+        bet.acceptor_proposal = 0;
+        bet.dispute_fallback = 0;
+        bet.arbiter = None;
+        bet.challenge_window = 0;
+        bet.challenge_deadline = None;
+        bet.disputed_by = None;
+        bet.dispute_bond = 0;
+        bet.oracle = OracleTerms::default();
+        bet.resolver_program = None;
+        bet.resolver_state = None;
+        bet.mint = None;
+        bet.protocol_fee_bps = 0;
+        bet.protocol_fee = 0;
+        bet.referee_fee_kind = 0;
+        bet.referee_fee = 0;
+        bet.referee_fee_paid = 0;
+        bet.referee_accepted_at = None;
+        bet.panel = RefereePanel::default();
+        bet.terms_revision = 0;
+        bet.counter_offer_count = 0;
+        bet.open_counter_offer_count = 0;
+        bet.settlement_proposer = None;
+        bet.settlement_creator_amount = 0;
+        bet.creator_position_holder = bet.creator;
+        bet.acceptor_position_holder = bet.acceptor;
+        bet.partial_fills = false;
+        bet.filled_amount = 0;
+        bet.filled_acceptor_amount = 0;
+        bet.fill_count = 0;
+        bet.open_fill_count = 0;
+        bet.linked_parlay_count = 0;
+        (bet.creator_deposit, bet.acceptor_deposit) = if bet.status == BetStatus::Open as u8 {
+            (bet.bet_amount, 0)
+        } else if bet.status == BetStatus::Accepted as u8 {
+            (bet.bet_amount, bet.acceptor_bet_amount()?)
+        } else {
+            (0, 0)
+        };
+        bet._v2_padding = [0; 1];
+    }
+
+    bet.version = BET_VERSION;
+    bet.try_serialize(&mut &mut bet_info.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
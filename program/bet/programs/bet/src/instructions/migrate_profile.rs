@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::constants::PROFILE_VERSION;
+use crate::state::profile::Profile;
+
+#[derive(Accounts)]
+pub struct MigrateProfile<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    /// CHECK: A profile written by an older version of the program is too small to load as the
+    /// current Profile, so its discriminator and wallet are checked by hand in migrate_profile
+    #[account(
+        mut,
+        owner = crate::ID
+    )]
+    pub profile: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Bring a profile up to PROFILE_VERSION, growing it to the current layout with the wallet paying
+// the extra rent. Older profiles can't be loaded by any other instruction until this has run.
+// Does nothing for a profile that is already current.
+pub fn migrate_profile(ctx: Context<MigrateProfile>) -> Result<()> {
+    let profile_info = ctx.accounts.profile.to_account_info();
+
+    {
+        let data = profile_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 + 32 && data[..8] == *Profile::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        require!(
+            data[8..40] == ctx.accounts.wallet.key().to_bytes(),
+            crate::error::BetError::InvalidProfileOwner
+        );
+    }
+
+    if profile_info.data_len() < Profile::LEN {
+        let rent_due = Rent::get()?
+            .minimum_balance(Profile::LEN)
+            .saturating_sub(profile_info.lamports());
+        if rent_due > 0 {
+            anchor_lang::solana_program::program::invoke(
+                &anchor_lang::solana_program::system_instruction::transfer(
+                    ctx.accounts.wallet.key,
+                    profile_info.key,
+                    rent_due,
+                ),
+                &[
+                    ctx.accounts.wallet.to_account_info(),
+                    profile_info.clone(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+        }
+        profile_info.resize(Profile::LEN)?;
+    }
+
+    // Every version only appended fields or took over padding that was always written as zero,
+    // and resize zero-extends, so an older profile reads as the current layout with the newer
+    // fields zeroed. The steps below spell out what each version starts from
+    let mut profile = Profile::try_deserialize(&mut &profile_info.try_borrow_data()?[..])?;
+    if profile.version >= PROFILE_VERSION {
+        return Ok(());
+    }
+
+    // Version 2: push and void counts. Bets that ended that way before the upgrade weren't counted
+    if profile.version < 2 {
+        profile.total_push_count = 0;
+        profile.total_void_count = 0;
+    }
+
+    // Version 3: the agreed settlement count
+    if profile.version < 3 {
+        profile.total_settled_count = 0;
+    }
+
+    // Version 4: the market count, read from bytes that were padding before
+    if profile.version < 4 {
+        profile.total_market_count = 0;
+        profile._padding = [0; 3];
+    }
+
+    // Version 5: the stats engine fields. Bets finished before the upgrade can't be replayed,
+    // so they start from zero - the version 1 counters are kept as they were
+    if profile.version < 5 {
+        profile.total_staked = 0;
+        profile.total_returned = 0;
+        profile.realized_profit = 0;
+        profile.roi_bps = 0;
+        profile.biggest_win = 0;
+        profile.current_streak = 0;
+        profile.longest_win_streak = 0;
+        profile.longest_loss_streak = 0;
+        profile._stats_padding = [0; 4];
+    }
+
+    // Version 6: exposure tracking. Bets already open or accepted weren't locked, so they
+    // aren't counted - releasing them later saturates at zero
+    if profile.version < 6 {
+        profile.open_bet_count = 0;
+        profile.active_bet_count = 0;
+        profile.locked_as_creator = 0;
+        profile.locked_as_acceptor = 0;
+        profile.max_exposure = None;
+        profile._exposure_padding = [0; 7];
+    }
+
+    profile.version = PROFILE_VERSION;
+    profile.try_serialize(&mut &mut profile_info.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
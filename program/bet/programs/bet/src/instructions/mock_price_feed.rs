@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::oracle::MockPriceFeed;
+
+#[derive(Accounts)]
+pub struct InitMockPriceFeed<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = MockPriceFeed::LEN
+    )]
+    pub price_feed: Account<'info, MockPriceFeed>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetMockPrice<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = price_feed.authority == authority.key() @ crate::error::BetError::Unauthorized
+    )]
+    pub price_feed: Account<'info, MockPriceFeed>,
+}
+
+pub fn init_mock_price_feed(
+    ctx: Context<InitMockPriceFeed>,
+    price: i64,
+    conf: u64,
+    expo: i32,
+    publish_time: i64,
+) -> Result<()> {
+    let price_feed = &mut ctx.accounts.price_feed;
+    
+    price_feed.authority = ctx.accounts.authority.key();
+    price_feed.price = price;
+    price_feed.conf = conf;
+    price_feed.expo = expo;
+    price_feed.publish_time = publish_time;
+    price_feed.version = 1;
+    price_feed._padding = [0; 7];
+    
+    Ok(())
+}
+
+pub fn set_mock_price(
+    ctx: Context<SetMockPrice>,
+    price: i64,
+    conf: u64,
+    expo: i32,
+    publish_time: i64,
+) -> Result<()> {
+    let price_feed = &mut ctx.accounts.price_feed;
+    
+    price_feed.price = price;
+    price_feed.conf = conf;
+    price_feed.expo = expo;
+    price_feed.publish_time = publish_time;
+    
+    Ok(())
+}
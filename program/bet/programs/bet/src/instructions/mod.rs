@@ -6,6 +6,39 @@ pub mod resolve_bet;
 pub mod delete_bet;
 pub mod add_friend;
 pub mod accept_friend;
+pub mod reclaim_expired_bet;
+pub mod refund_unresolved_bet;
+pub mod propose_outcome;
+pub mod dispute_outcome;
+pub mod finalize_bet;
+pub mod settle_with_oracle;
+pub mod initialize_config;
+pub mod update_config;
+pub mod withdraw_fees;
+pub mod accept_referee_role;
+pub mod decline_referee_role;
+pub mod cast_referee_vote;
+pub mod update_bet;
+pub mod create_counter_offer;
+pub mod accept_counter_offer;
+pub mod close_counter_offer;
+pub mod propose_settlement;
+pub mod accept_settlement;
+pub mod transfer_position;
+pub mod fill_bet;
+pub mod resolve_pool_bet;
+pub mod claim_fill;
+pub mod create_market;
+pub mod stake_market;
+pub mod resolve_market;
+pub mod claim_market_stake;
+pub mod delete_market;
+pub mod settle_parlay;
+pub mod migrate_profile;
+pub mod migrate_bet;
+pub mod set_max_exposure;
+#[cfg(feature = "mock-oracle")]
+pub mod mock_price_feed;
 
 pub use create_profile::*;
 pub use create_bet::*;
@@ -15,4 +48,37 @@ pub use resolve_bet::*;
 pub use delete_bet::*;
 pub use add_friend::*;
 pub use accept_friend::*;
+pub use reclaim_expired_bet::*;
+pub use refund_unresolved_bet::*;
+pub use propose_outcome::*;
+pub use dispute_outcome::*;
+pub use finalize_bet::*;
+pub use settle_with_oracle::*;
+pub use initialize_config::*;
+pub use update_config::*;
+pub use withdraw_fees::*;
+pub use accept_referee_role::*;
+pub use decline_referee_role::*;
+pub use cast_referee_vote::*;
+pub use update_bet::*;
+pub use create_counter_offer::*;
+pub use accept_counter_offer::*;
+pub use close_counter_offer::*;
+pub use propose_settlement::*;
+pub use accept_settlement::*;
+pub use transfer_position::*;
+pub use fill_bet::*;
+pub use resolve_pool_bet::*;
+pub use claim_fill::*;
+pub use create_market::*;
+pub use stake_market::*;
+pub use resolve_market::*;
+pub use claim_market_stake::*;
+pub use delete_market::*;
+pub use settle_parlay::*;
+pub use migrate_profile::*;
+pub use migrate_bet::*;
+pub use set_max_exposure::*;
+#[cfg(feature = "mock-oracle")]
+pub use mock_price_feed::*;
 
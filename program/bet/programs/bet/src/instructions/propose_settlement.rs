@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::bet::{Bet, BetStatus};
+
+#[derive(Accounts)]
+pub struct ProposeSettlement<'info> {
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = !bet.partial_fills @ crate::error::BetError::PoolBet,
+        constraint = bet.status == BetStatus::Accepted as u8 @ crate::error::BetError::InvalidBetStatus
+    )]
+    pub bet: Account<'info, Bet>,
+}
+
+// Offer to end an accepted bet early, with `creator_amount` of the pot going to the creator
+// and the rest to the acceptor. A new proposal from either side replaces the previous one.
+pub fn propose_settlement(ctx: Context<ProposeSettlement>, creator_amount: u64) -> Result<()> {
+    let bet = &mut ctx.accounts.bet;
+    let proposer = ctx.accounts.proposer.key();
+    let clock = Clock::get()?;
+
+    // Whoever holds a side's position now negotiates for it
+    require!(
+        proposer == bet.creator_position_holder || Some(proposer) == bet.acceptor_position_holder,
+        crate::error::BetError::Unauthorized
+    );
+
+    // Once the resolution deadline passes the bet can only be refunded
+    require!(
+        clock.unix_timestamp < bet.resolve_by,
+        crate::error::BetError::ResolutionDeadlinePassed
+    );
+
+    require!(
+        creator_amount <= bet.deposits()?,
+        crate::error::BetError::SettlementMismatch
+    );
+
+    bet.settlement_proposer = Some(proposer);
+    bet.settlement_creator_amount = creator_amount;
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::constants::RECLAIM_EXPIRED_BET_INCENTIVE;
+use crate::state::bet::{Bet, BetStatus, PositionSide};
+use crate::state::profile::Profile;
+
+#[derive(Accounts)]
+pub struct ReclaimExpiredBet<'info> {
+    /// CHECK: Anyone can call this instruction (permissionless), receives the cleanup incentive
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    /// CHECK: Creator account to receive the refund (validated by bet.creator)
+    #[account(mut)]
+    pub creator: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"username-", creator_profile.name.as_ref()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.wallet == creator.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub creator_profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        constraint = bet.creator == creator.key() @ crate::error::BetError::InvalidBetCreator,
+        constraint = bet.status == BetStatus::Open as u8 || bet.status == BetStatus::PendingReferee as u8 @ crate::error::BetError::InvalidBetStatus,
+        constraint = bet.acceptor.is_none() @ crate::error::BetError::BetAlreadyAccepted
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// CHECK: Treasury PDA for holding bet funds (must exist from create_bet)
+    #[account(
+        mut,
+        seeds = [b"bet-treasury-", bet.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Treasury account
+    pub treasury: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // SPL bets only - leave these out for SOL bets
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn reclaim_expired_bet(ctx: Context<ReclaimExpiredBet>) -> Result<()> {
+    let treasury = crate::treasury::BetTreasury::new(
+        &ctx.accounts.bet,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+
+    let bet = &mut ctx.accounts.bet;
+    let clock = Clock::get()?;
+
+    // Validate bet has expired
+    require!(
+        clock.unix_timestamp >= bet.expires_at,
+        crate::error::BetError::BetNotExpired
+    );
+
+    // A pool bet with fills only gives back the unfilled part of the creator's stake, the rest
+    // of it backs the fills. Otherwise the creator's whole stake comes back
+    let pool_filled = bet.partial_fills && bet.filled_amount > 0;
+    let reclaimable = bet.creator_deposit
+        .checked_sub(bet.filled_amount)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+    bet.creator_deposit = bet.filled_amount;
+
+    // Pay the caller a small incentive for cleaning up, capped at what can be reclaimed.
+    // The incentive is in lamports, so SPL bets don't pay one
+    let incentive = if treasury.is_token() {
+        0
+    } else {
+        reclaimable.min(RECLAIM_EXPIRED_BET_INCENTIVE)
+    };
+    treasury.pay(&ctx.accounts.caller.to_account_info(), None, incentive)?;
+
+    // Return the rest to the creator
+    let refund = reclaimable
+        .checked_sub(incentive)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+    let creator = ctx.accounts.creator.to_account_info();
+    let creator_token_account = ctx.accounts.creator_token_account.as_ref().map(|account| account.to_account_info());
+    treasury.pay(&creator, creator_token_account.clone(), refund)?;
+    treasury.sweep_excess(bet, &creator, creator_token_account)?;
+
+    let creator_profile = &mut ctx.accounts.creator_profile;
+    creator_profile.open_bet_count = creator_profile.open_bet_count.saturating_sub(1);
+    creator_profile.unlock_stake(PositionSide::Creator, reclaimable);
+
+    if pool_filled {
+        // The pool closes with what was filled and waits on the referee like an accepted bet
+        bet.status = BetStatus::Accepted as u8;
+        bet.accepted_at = Some(clock.unix_timestamp);
+        creator_profile.active_bet_count += 1;
+    } else {
+        // Expired is distinct from Cancelled so the creator's cancelled_bet_count is untouched
+        bet.status = BetStatus::Expired as u8;
+    }
+
+    Ok(())
+}
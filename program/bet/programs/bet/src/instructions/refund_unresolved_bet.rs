@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::instructions::resolve_bet::position_payee;
+use crate::state::bet::{Bet, BetStatus, BetOutcome, DisputeFallback, PositionSide};
+use crate::state::profile::Profile;
+
+#[derive(Accounts)]
+pub struct RefundUnresolvedBet<'info> {
+    /// CHECK: Anyone can call this instruction (permissionless)
+    pub caller: Signer<'info>,
+
+    /// CHECK: Creator is validated by checking bet.creator matches this account (mut for SOL transfer)
+    #[account(mut)]
+    pub creator: AccountInfo<'info>,
+
+    /// CHECK: Acceptor is validated by checking bet.acceptor matches this account (mut for SOL transfer)
+    #[account(mut)]
+    pub acceptor: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"username-", creator_profile.name.as_ref()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.wallet == creator.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub creator_profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        seeds = [b"username-", acceptor_profile.name.as_ref()],
+        bump = acceptor_profile.bump,
+        constraint = acceptor_profile.wallet == acceptor.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub acceptor_profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        constraint = bet.creator == creator.key() @ crate::error::BetError::InvalidBetCreator,
+        constraint = bet.status == BetStatus::Accepted as u8 || bet.status == BetStatus::Disputed as u8 @ crate::error::BetError::InvalidBetStatus,
+        constraint = bet.acceptor == Some(acceptor.key()) @ crate::error::BetError::BetNotAccepted
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// CHECK: Treasury PDA for holding bet funds (must exist from create_bet)
+    #[account(
+        mut,
+        seeds = [b"bet-treasury-", bet.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Treasury account
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Current holder of the creator's position, validated against bet.creator_position_holder (only needed once it has been transferred)
+    #[account(mut)]
+    pub creator_position_holder: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Current holder of the acceptor's position, validated against bet.acceptor_position_holder (only needed once it has been transferred)
+    #[account(mut)]
+    pub acceptor_position_holder: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+
+    // SPL bets only - leave these out for SOL bets
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub acceptor_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn refund_unresolved_bet(ctx: Context<RefundUnresolvedBet>) -> Result<()> {
+    let treasury = crate::treasury::BetTreasury::new(
+        &ctx.accounts.bet,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+
+    let bet = &mut ctx.accounts.bet;
+    let clock = Clock::get()?;
+
+    // Disputes with a refund fallback are refunded straight away, otherwise the
+    // referee (or arbiter) had until resolve_by to resolve the bet
+    let refund_dispute = bet.status == BetStatus::Disputed as u8
+        && bet.dispute_fallback == DisputeFallback::Refund as u8;
+    require!(
+        refund_dispute || clock.unix_timestamp >= bet.resolve_by,
+        crate::error::BetError::ResolutionDeadlineNotReached
+    );
+
+    // Refunds go to whoever holds each side's position now (a dispute bond travels with the position)
+    let creator_payee = position_payee(
+        &ctx.accounts.creator.to_account_info(),
+        ctx.accounts.creator_position_holder.as_ref().map(|account| account.as_ref()),
+        Some(bet.creator_position_holder),
+    )?;
+    let acceptor_payee = position_payee(
+        &ctx.accounts.acceptor.to_account_info(),
+        ctx.accounts.acceptor_position_holder.as_ref().map(|account| account.as_ref()),
+        bet.acceptor_position_holder,
+    )?;
+
+    // Neither side's stake is locked any more
+    let creator_profile = &mut ctx.accounts.creator_profile;
+    creator_profile.active_bet_count = creator_profile.active_bet_count.saturating_sub(1);
+    creator_profile.unlock_stake(PositionSide::Creator, bet.creator_deposit);
+    let acceptor_profile = &mut ctx.accounts.acceptor_profile;
+    acceptor_profile.active_bet_count = acceptor_profile.active_bet_count.saturating_sub(1);
+    acceptor_profile.unlock_stake(PositionSide::Acceptor, bet.acceptor_deposit);
+
+    // Each side gets back exactly what it put in, plus the dispute bond if the arbiter
+    // never ruled on its dispute
+    let mut acceptor_refund = bet.acceptor_deposit;
+    let mut creator_refund = bet.creator_deposit;
+    if bet.disputed_by == bet.acceptor {
+        acceptor_refund = acceptor_refund
+            .checked_add(bet.dispute_bond)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+    } else {
+        creator_refund = creator_refund
+            .checked_add(bet.dispute_bond)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+    }
+    bet.acceptor_deposit = 0;
+    bet.creator_deposit = 0;
+    bet.dispute_bond = 0;
+
+    let creator_token_account = ctx.accounts.creator_token_account.as_ref().map(|account| account.to_account_info());
+    treasury.pay(
+        &acceptor_payee,
+        ctx.accounts.acceptor_token_account.as_ref().map(|account| account.to_account_info()),
+        acceptor_refund,
+    )?;
+    treasury.pay(&creator_payee, creator_token_account.clone(), creator_refund)?;
+    treasury.sweep_excess(bet, &creator_payee, creator_token_account)?;
+
+    bet.status = BetStatus::Voided as u8;
+    bet.outcome = BetOutcome::Void as u8;
+    bet.resolved_at = Some(clock.unix_timestamp);
+
+    Ok(())
+}
@@ -1,13 +1,19 @@
 use anchor_lang::prelude::*;
-use crate::state::bet::{Bet, BetStatus};
-use crate::state::profile::Profile;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::math::{bps_of, pro_rata};
+use crate::state::bet::{Bet, BetStatus, BetOutcome, RefereeType, DisputeFallback, PositionSide};
+use crate::state::config::Config;
+use crate::state::profile::{BetResult, Profile};
+use crate::treasury::BetTreasury;
 
 #[derive(Accounts)]
 pub struct ResolveBet<'info> {
     #[account(mut)]
     pub resolver: Signer<'info>,
     
-    /// CHECK: Referee account (creator for Honor System, designated for Third Party)
+    /// CHECK: Referee account (creator for Honor System, designated for Third Party, resolver PDA for Smart Contract)
+    /// (mut to receive the referee fee)
+    #[account(mut)]
     pub referee: AccountInfo<'info>,
     
     /// CHECK: Creator is validated by checking bet.creator matches this account (mut for SOL transfer)
@@ -37,8 +43,8 @@ pub struct ResolveBet<'info> {
     #[account(
         mut,
         constraint = bet.creator == creator.key() @ crate::error::BetError::InvalidBetCreator,
-        constraint = bet.status == BetStatus::Accepted as u8 @ crate::error::BetError::InvalidBetStatus,
-        constraint = bet.acceptor.is_some() @ crate::error::BetError::BetNotAccepted
+        constraint = bet.status == BetStatus::Accepted as u8 || bet.status == BetStatus::Disputed as u8 @ crate::error::BetError::InvalidBetStatus,
+        constraint = bet.acceptor == Some(acceptor.key()) @ crate::error::BetError::BetNotAccepted
     )]
     pub bet: Account<'info, Bet>,
     
@@ -51,139 +57,354 @@ pub struct ResolveBet<'info> {
     /// CHECK: Treasury account
     pub treasury: UncheckedAccount<'info>,
     
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// CHECK: Fee vault PDA receiving the protocol fee
+    #[account(
+        mut,
+        seeds = [b"fee-vault"],
+        bump = config.fee_vault_bump
+    )]
+    pub fee_vault: UncheckedAccount<'info>,
+    
+    /// CHECK: Current holder of the creator's position, validated against bet.creator_position_holder (only needed once it has been transferred)
+    #[account(mut)]
+    pub creator_position_holder: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Current holder of the acceptor's position, validated against bet.acceptor_position_holder (only needed once it has been transferred)
+    #[account(mut)]
+    pub acceptor_position_holder: Option<UncheckedAccount<'info>>,
+    
     pub system_program: Program<'info, System>,
+
+    // SPL bets only - leave these out for SOL bets
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(mut)]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(mut)]
+    pub acceptor_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(mut)]
+    pub fee_vault_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(mut)]
+    pub referee_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 pub fn resolve_bet(
     ctx: Context<ResolveBet>,
-    winner_is_creator: bool,
+    outcome: u8,
 ) -> Result<()> {
-    // Verify referee matches bet.referee
+    // Validate outcome - Pending is not a valid resolution
+    let outcome = BetOutcome::try_from(outcome)?;
     require!(
-        ctx.accounts.referee.key() == ctx.accounts.bet.referee,
-        crate::error::BetError::Unauthorized
-    );
-    
-    // Verify resolver (signer) is the referee
-    require!(
-        ctx.accounts.resolver.key() == ctx.accounts.referee.key(),
-        crate::error::BetError::Unauthorized
+        outcome != BetOutcome::Pending,
+        crate::error::BetError::InvalidOutcome
     );
     
-    // For Honor System, verify referee is the creator
-    if ctx.accounts.bet.referee_type == 0 {
+    if ctx.accounts.bet.status == BetStatus::Disputed as u8 {
+        // Disputed bets can only be resolved by the arbiter named at creation
         require!(
-            ctx.accounts.referee.key() == ctx.accounts.creator.key(),
+            ctx.accounts.bet.dispute_fallback == DisputeFallback::Arbiter as u8,
+            crate::error::BetError::InvalidBetStatus
+        );
+        require!(
+            ctx.accounts.bet.arbiter == Some(ctx.accounts.resolver.key()),
             crate::error::BetError::Unauthorized
         );
+    } else {
+        check_referee(
+            ctx.accounts.bet.referee_type,
+            &ctx.accounts.bet.referee,
+            &ctx.accounts.resolver.key(),
+            &ctx.accounts.referee.key(),
+            &ctx.accounts.creator.key(),
+        )?;
     }
     
-    // Get bet key before mutable borrow
-    let bet_key = ctx.accounts.bet.key();
-    let treasury_bump = ctx.bumps.treasury;
-    
-    let bet = &mut ctx.accounts.bet;
-    let creator_profile = &mut ctx.accounts.creator_profile;
-    let acceptor_profile = &mut ctx.accounts.acceptor_profile;
+    // Once the resolution deadline passes the bet can only be refunded
     let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp < ctx.accounts.bet.resolve_by,
+        crate::error::BetError::ResolutionDeadlinePassed
+    );
     
-    // Determine winner
-    let winner = if winner_is_creator {
-        ctx.accounts.creator.key()
-    } else {
-        ctx.accounts.acceptor.key()
-    };
+    let treasury = BetTreasury::new(
+        &ctx.accounts.bet,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+    let creator_token_account = ctx.accounts.creator_token_account.as_ref().map(|account| account.to_account_info());
+    let acceptor_token_account = ctx.accounts.acceptor_token_account.as_ref().map(|account| account.to_account_info());
     
-    bet.winner = Some(winner);
-    bet.status = BetStatus::Resolved as u8;
-    bet.resolved_at = Some(clock.unix_timestamp);
+    let bet = &mut ctx.accounts.bet;
     
-    // Get treasury balance (all SOL in treasury)
-    let treasury_balance = ctx.accounts.treasury.lamports();
+    if bet.status == BetStatus::Disputed as u8 {
+        // The arbiter's ruling is final - settle the dispute bond before paying out.
+        // If the pending outcome is upheld the bond goes to the other side, otherwise it goes back to the disputer
+        if bet.dispute_bond > 0 {
+            let upheld = outcome as u8 == bet.outcome;
+            let disputer_is_creator = bet.disputed_by == Some(bet.creator);
+            // Bond travels with the position, like the rest of that side's payout
+            let (bond_recipient, bond_recipient_token_account) = if disputer_is_creator != upheld {
+                let creator_payee = position_payee(
+                    &ctx.accounts.creator.to_account_info(),
+                    ctx.accounts.creator_position_holder.as_ref().map(|account| account.as_ref()),
+                    Some(bet.creator_position_holder),
+                )?;
+                (creator_payee, creator_token_account.clone())
+            } else {
+                let acceptor_payee = position_payee(
+                    &ctx.accounts.acceptor.to_account_info(),
+                    ctx.accounts.acceptor_position_holder.as_ref().map(|account| account.as_ref()),
+                    bet.acceptor_position_holder,
+                )?;
+                (acceptor_payee, acceptor_token_account.clone())
+            };
+            treasury.pay(&bond_recipient, bond_recipient_token_account, bet.dispute_bond)?;
+            bet.dispute_bond = 0;
+        }
+    } else if bet.challenge_window > 0 {
+        // Record the outcome as pending - finalize_bet pays out once the challenge window closes undisputed
+        bet.outcome = outcome as u8;
+        bet.status = BetStatus::PendingFinalization as u8;
+        bet.challenge_deadline = Some(
+            clock.unix_timestamp
+                .checked_add(bet.challenge_window)
+                .ok_or(crate::error::BetError::ArithmeticOverflow)?
+        );
+        return Ok(());
+    }
     
-    // Transfer all SOL from treasury to winner using system program
-    // Must use invoke_signed because treasury is a PDA and needs program signature
-    if treasury_balance > 0 {
-        let winner_account = if winner_is_creator {
-            ctx.accounts.creator.to_account_info()
+    Settlement {
+        creator: ctx.accounts.creator.to_account_info(),
+        acceptor: ctx.accounts.acceptor.to_account_info(),
+        creator_position_holder: ctx.accounts.creator_position_holder.as_ref().map(|account| account.to_account_info()),
+        acceptor_position_holder: ctx.accounts.acceptor_position_holder.as_ref().map(|account| account.to_account_info()),
+        creator_profile: &mut ctx.accounts.creator_profile,
+        acceptor_profile: &mut ctx.accounts.acceptor_profile,
+        bet: &mut ctx.accounts.bet,
+        creator_token_account,
+        acceptor_token_account,
+        treasury,
+        fee_bps: ctx.accounts.config.fee_bps,
+        fee_vault: ctx.accounts.fee_vault.to_account_info(),
+        fee_vault_token_account: ctx.accounts.fee_vault_token_account.as_ref().map(|account| account.to_account_info()),
+        referee: Some(ctx.accounts.referee.to_account_info()),
+        referee_token_account: ctx.accounts.referee_token_account.as_ref().map(|account| account.to_account_info()),
+    }
+    .settle(outcome)
+}
+
+// Accounts needed to pay out an accepted bet from its treasury.
+// Shared by every instruction that settles a bet so the payout and stats logic live in one place.
+pub struct Settlement<'a, 'info> {
+    pub creator: AccountInfo<'info>,
+    pub acceptor: AccountInfo<'info>,
+    pub creator_position_holder: Option<AccountInfo<'info>>,  // Only needed once the position has been transferred
+    pub acceptor_position_holder: Option<AccountInfo<'info>>, // Only needed once the position has been transferred
+    pub creator_profile: &'a mut Account<'info, Profile>,
+    pub acceptor_profile: &'a mut Account<'info, Profile>,
+    pub bet: &'a mut Account<'info, Bet>,
+    pub creator_token_account: Option<AccountInfo<'info>>,   // SPL bets only
+    pub acceptor_token_account: Option<AccountInfo<'info>>,  // SPL bets only
+    pub treasury: BetTreasury<'info>,
+    pub fee_bps: u16,                                         // Protocol fee rate from Config
+    pub fee_vault: AccountInfo<'info>,
+    pub fee_vault_token_account: Option<AccountInfo<'info>>, // SPL bets only
+    pub referee: Option<AccountInfo<'info>>,                 // Only needed when the bet pays its referee
+    pub referee_token_account: Option<AccountInfo<'info>>,   // SPL bets only
+}
+
+impl<'a, 'info> Settlement<'a, 'info> {
+    pub fn settle(self, outcome: BetOutcome) -> Result<()> {
+        let treasury = self.treasury;
+        let bet = self.bet;
+        let creator_profile = self.creator_profile;
+        let acceptor_profile = self.acceptor_profile;
+        let clock = Clock::get()?;
+        
+        // Payouts go to whoever holds each side's position now, stats stay with the original parties
+        let creator_payee = position_payee(&self.creator, self.creator_position_holder.as_ref(), Some(bet.creator_position_holder))?;
+        let acceptor_payee = position_payee(&self.acceptor, self.acceptor_position_holder.as_ref(), bet.acceptor_position_holder)?;
+        
+        // A disputed bet still holds the referee's pending outcome, so a different final outcome means the arbiter overturned it
+        let referee_overruled = bet.status == BetStatus::Disputed as u8 && bet.outcome != outcome as u8;
+        
+        bet.outcome = outcome as u8;
+        bet.status = BetStatus::Resolved as u8;
+        bet.resolved_at = Some(clock.unix_timestamp);
+        
+        // What each side put in, for the profile stats. Neither stake is locked any more
+        let creator_staked = bet.creator_deposit;
+        let acceptor_staked = bet.acceptor_deposit;
+        creator_profile.active_bet_count = creator_profile.active_bet_count.saturating_sub(1);
+        creator_profile.unlock_stake(PositionSide::Creator, creator_staked);
+        acceptor_profile.active_bet_count = acceptor_profile.active_bet_count.saturating_sub(1);
+        acceptor_profile.unlock_stake(PositionSide::Acceptor, acceptor_staked);
+        
+        // A paid referee gets their fee out of the pot on any resolution (refunds never reach here),
+        // unless an arbiter overturned the referee's outcome in a dispute - then the fee stays in the pot.
+        // Each side pays a share in proportion to its recorded stake, the creator's side takes the rounding
+        let referee_fee = if bet.referee_fee > 0 && !referee_overruled {
+            bet.referee_fee_amount()?
         } else {
-            ctx.accounts.acceptor.to_account_info()
+            0
         };
+        if referee_fee > 0 {
+            let referee = self.referee.as_ref().ok_or(crate::error::BetError::Unauthorized)?;
+            require!(
+                referee.key() == bet.referee,
+                crate::error::BetError::Unauthorized
+            );
+            let acceptor_fee_share = pro_rata(referee_fee, bet.acceptor_deposit, bet.deposits()?)?;
+            let creator_fee_share = referee_fee
+                .checked_sub(acceptor_fee_share)
+                .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+            bet.acceptor_deposit = bet.acceptor_deposit
+                .checked_sub(acceptor_fee_share)
+                .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+            bet.creator_deposit = bet.creator_deposit
+                .checked_sub(creator_fee_share)
+                .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+            treasury.pay(referee, self.referee_token_account, referee_fee)?;
+            bet.referee_fee_paid = referee_fee;
+        }
         
-        // Prepare seeds for PDA signing
-        let seeds = &[
-            b"bet-treasury-",
-            bet_key.as_ref(),
-            &[treasury_bump],
-        ];
-        let signer_seeds = &[&seeds[..]];
+        // Push and Void have no winner - each side gets back what it put in, less its share of the referee fee
+        if outcome == BetOutcome::Push || outcome == BetOutcome::Void {
+            bet.winner = None;
+            
+            let acceptor_refund = bet.acceptor_deposit;
+            let creator_refund = bet.creator_deposit;
+            bet.acceptor_deposit = 0;
+            bet.creator_deposit = 0;
+            treasury.pay(&acceptor_payee, self.acceptor_token_account, acceptor_refund)?;
+            treasury.pay(&creator_payee, self.creator_token_account.clone(), creator_refund)?;
+            treasury.sweep_excess(bet, &creator_payee, self.creator_token_account)?;
+            
+            // Pushes and voids are not wins or losses, track them separately
+            let result = if outcome == BetOutcome::Push { BetResult::Push } else { BetResult::Void };
+            creator_profile.record_result(PositionSide::Creator, result, creator_staked, creator_refund)?;
+            acceptor_profile.record_result(PositionSide::Acceptor, result, acceptor_staked, acceptor_refund)?;
+            
+            return Ok(());
+        }
         
-        anchor_lang::solana_program::program::invoke_signed(
-            &anchor_lang::solana_program::system_instruction::transfer(
-                ctx.accounts.treasury.key,
-                winner_account.key,
-                treasury_balance,
-            ),
-            &[
-                ctx.accounts.treasury.to_account_info(),
-                winner_account,
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            signer_seeds,
-        )?;
-    }
-    
-    // Calculate profit for winner (payout amount using creator win ratio calc)
-    let payout_amount = (bet.bet_amount as u128)
-        .checked_mul(bet.odds_win as u128)
-        .and_then(|x| x.checked_div(bet.odds_lose as u128))
-        .ok_or(crate::error::BetError::ArithmeticOverflow)? as u64;
-    
-    // Calculate rewarded amount for volume tracking
-    // If creator loses: both volumes increase by bet_amount
-    // If creator wins: both volumes increase by payout_amount (creator win ratio calc)
-    let rewarded_amount = if winner_is_creator {
-        payout_amount
-    } else {
-        bet.bet_amount
-    };
-    
-    // Update volume stats (increment by rewarded amount for both parties)
-    creator_profile.total_my_bet_volume = creator_profile.total_my_bet_volume
-        .checked_add(rewarded_amount)
-        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
-    
-    acceptor_profile.total_accepted_bet_volume = acceptor_profile.total_accepted_bet_volume
-        .checked_add(rewarded_amount)
-        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
-    
-    // Calculate profit for profile stats (net profit, not total payout)
-    let profit = payout_amount;
-    
-    if winner_is_creator {
-        // Creator wins
-        creator_profile.total_my_bet_wins += 1;
-        creator_profile.total_my_bet_profit = creator_profile.total_my_bet_profit
-            .checked_add(profit as i64)
-            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+        // Determine winner
+        let winner_is_creator = outcome == BetOutcome::CreatorWins;
+        let (winner_account, winner_payee, winner_token_account) = if winner_is_creator {
+            (&self.creator, &creator_payee, self.creator_token_account.clone())
+        } else {
+            (&self.acceptor, &acceptor_payee, self.acceptor_token_account)
+        };
         
-        acceptor_profile.total_accepted_bet_losses += 1;
-        acceptor_profile.total_accepted_bet_profit = acceptor_profile.total_accepted_bet_profit
-            .checked_sub(bet.bet_amount as i64)
-            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
-    } else {
-        // Acceptor wins
-        acceptor_profile.total_accepted_bet_wins += 1;
-        acceptor_profile.total_accepted_bet_profit = acceptor_profile.total_accepted_bet_profit
-            .checked_add(profit as i64)
-            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+        bet.winner = Some(winner_account.key());
         
-        creator_profile.total_my_bet_losses += 1;
-        creator_profile.total_my_bet_profit = creator_profile.total_my_bet_profit
-            .checked_sub(bet.bet_amount as i64)
+        // Protocol fee is skimmed from the pot before the winner is paid, and recorded
+        // on the bet so the fee stays auditable if the configured rate changes later
+        let stakes = bet.deposits()?;
+        let protocol_fee = bps_of(stakes, self.fee_bps as u64)?;
+        let winnings = stakes
+            .checked_sub(protocol_fee)
             .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+        bet.creator_deposit = 0;
+        bet.acceptor_deposit = 0;
+        
+        treasury.pay(&self.fee_vault, self.fee_vault_token_account, protocol_fee)?;
+        bet.protocol_fee_bps = self.fee_bps;
+        bet.protocol_fee = protocol_fee;
+        
+        // Both stakes, less fees, go to the winning position's holder
+        treasury.pay(winner_payee, winner_token_account, winnings)?;
+        treasury.sweep_excess(bet, &creator_payee, self.creator_token_account)?;
+        
+        // Stats stay with the original parties: the winner's return is the whole payout, the loser's is nothing
+        let (creator_result, creator_returned, acceptor_result, acceptor_returned) = if winner_is_creator {
+            (BetResult::Win, winnings, BetResult::Loss, 0)
+        } else {
+            (BetResult::Loss, 0, BetResult::Win, winnings)
+        };
+        creator_profile.record_result(PositionSide::Creator, creator_result, creator_staked, creator_returned)?;
+        acceptor_profile.record_result(PositionSide::Acceptor, acceptor_result, acceptor_staked, acceptor_returned)?;
+        
+        Ok(())
+    }
+}
+
+// Wallet to pay for one side of the bet: the original party, or the holder passed in if the
+// position has been transferred (SPL payouts then need a token account owned by the holder)
+pub fn position_payee<'info>(
+    party: &AccountInfo<'info>,
+    holder: Option<&AccountInfo<'info>>,
+    position_holder: Option<Pubkey>,
+) -> Result<AccountInfo<'info>> {
+    match position_holder {
+        Some(position_holder) if position_holder != party.key() => {
+            let holder = holder.ok_or(crate::error::BetError::InvalidPositionHolder)?;
+            require!(
+                holder.key() == position_holder,
+                crate::error::BetError::InvalidPositionHolder
+            );
+            Ok(holder.clone())
+        }
+        _ => Ok(party.clone()),
+    }
+}
+
+// Only the referee can resolve a bet outside a dispute (or a market). Mutual Consent bets are settled through
+// propose_outcome, Oracle bets through settle_with_oracle, Panel bets through cast_referee_vote and
+// Parlay bets through settle_parlay, never by a single signer
+pub fn check_referee(
+    referee_type: u8,
+    expected_referee: &Pubkey,
+    resolver: &Pubkey,
+    referee: &Pubkey,
+    creator: &Pubkey,
+) -> Result<()> {
+    require!(
+        referee_type != RefereeType::MutualConsent as u8
+            && referee_type != RefereeType::Oracle as u8
+            && referee_type != RefereeType::Panel as u8
+            && referee_type != RefereeType::Parlay as u8,
+        crate::error::BetError::InvalidRefereeType
+    );
+    
+    // Verify referee matches the one recorded on the bet (or market)
+    require!(
+        referee == expected_referee,
+        crate::error::BetError::Unauthorized
+    );
+    
+    // Verify resolver (signer) is the referee. For Smart Contract bets the referee is a PDA
+    // of the resolver program, so this only passes inside a CPI signed by that program
+    require!(
+        resolver == referee,
+        crate::error::BetError::Unauthorized
+    );
+    
+    // For Honor System, verify referee is the creator
+    if referee_type == RefereeType::HonorSystem as u8 {
+        require!(
+            referee == creator,
+            crate::error::BetError::Unauthorized
+        );
     }
     
     Ok(())
 }
-
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::instructions::resolve_bet::check_referee;
+use crate::math::bps_of;
+use crate::state::bet::BetStatus;
+use crate::state::config::Config;
+use crate::state::market::Market;
+use crate::treasury::BetTreasury;
+
+#[derive(Accounts)]
+pub struct ResolveMarket<'info> {
+    /// CHECK: The referee, or anyone voiding the market once resolve_by has passed
+    pub resolver: Signer<'info>,
+
+    /// CHECK: Referee account (creator for Honor System, designated for Third Party, resolver PDA for Smart Contract)
+    pub referee: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = market.status == BetStatus::Open as u8 @ crate::error::BetError::InvalidBetStatus
+    )]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: Treasury PDA holding the market's stakes
+    #[account(
+        mut,
+        seeds = [b"market-treasury-", market.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Treasury account
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Fee vault PDA receiving the protocol fee
+    #[account(
+        mut,
+        seeds = [b"fee-vault"],
+        bump = config.fee_vault_bump
+    )]
+    pub fee_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // SPL markets only - leave these out for SOL markets
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub fee_vault_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+// Name the winning outcome once staking has closed, or void the market with None. Stakers
+// are then paid one stake at a time with claim_market_stake. Once resolve_by passes anyone
+// can void the market instead.
+pub fn resolve_market(ctx: Context<ResolveMarket>, winning_outcome: Option<u8>) -> Result<()> {
+    let clock = Clock::get()?;
+    let market = &ctx.accounts.market;
+
+    let refund = clock.unix_timestamp >= market.resolve_by;
+    if refund {
+        // Once the resolution deadline passes the market can only be refunded
+        require!(
+            winning_outcome.is_none(),
+            crate::error::BetError::ResolutionDeadlinePassed
+        );
+    } else {
+        check_referee(
+            market.referee_type,
+            &market.referee,
+            &ctx.accounts.resolver.key(),
+            &ctx.accounts.referee.key(),
+            &market.creator,
+        )?;
+        // Nobody can stake on an outcome that is already known
+        require!(
+            clock.unix_timestamp >= market.expires_at,
+            crate::error::BetError::BetNotExpired
+        );
+    }
+
+    if let Some(outcome) = winning_outcome {
+        require!(
+            outcome < market.outcome_count,
+            crate::error::BetError::InvalidMarketOutcome
+        );
+    }
+
+    let treasury = BetTreasury::for_market(
+        &ctx.accounts.market,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+
+    let market = &mut ctx.accounts.market;
+    market.resolved_at = Some(clock.unix_timestamp);
+
+    // Nobody backed the winner, so there is nobody to share the pool with - refund everyone
+    let winning_outcome = winning_outcome.filter(|outcome| market.outcome_pools[*outcome as usize] > 0);
+    let outcome = match winning_outcome {
+        Some(outcome) => outcome,
+        None => {
+            market.status = BetStatus::Voided as u8;
+            market.winning_outcome = None;
+            return Ok(());
+        }
+    };
+
+    // Protocol fee is skimmed from the whole pool, the rest is shared by the winning stakes
+    let protocol_fee = bps_of(market.total_pool, ctx.accounts.config.fee_bps as u64)?;
+    treasury.pay(
+        &ctx.accounts.fee_vault.to_account_info(),
+        ctx.accounts.fee_vault_token_account.as_ref().map(|account| account.to_account_info()),
+        protocol_fee,
+    )?;
+
+    market.status = BetStatus::Resolved as u8;
+    market.winning_outcome = Some(outcome);
+    market.protocol_fee_bps = ctx.accounts.config.fee_bps;
+    market.protocol_fee = protocol_fee;
+
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::math::bps_of;
+use crate::instructions::resolve_bet::check_referee;
+use crate::state::bet::{Bet, BetStatus, BetOutcome, PositionSide};
+use crate::state::config::Config;
+use crate::state::profile::{BetResult, Profile};
+use crate::treasury::BetTreasury;
+
+#[derive(Accounts)]
+pub struct ResolvePoolBet<'info> {
+    /// CHECK: The referee, or anyone voiding the pool once resolve_by has passed
+    pub resolver: Signer<'info>,
+
+    /// CHECK: Referee account (creator for Honor System, designated for Third Party, resolver PDA for Smart Contract)
+    pub referee: AccountInfo<'info>,
+
+    /// CHECK: Creator is validated by checking bet.creator matches this account (mut for SOL transfer)
+    #[account(mut)]
+    pub creator: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"username-", creator_profile.name.as_ref()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.wallet == creator.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub creator_profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        constraint = bet.creator == creator.key() @ crate::error::BetError::InvalidBetCreator,
+        constraint = bet.partial_fills @ crate::error::BetError::NotPoolBet,
+        constraint = bet.status == BetStatus::Accepted as u8 @ crate::error::BetError::InvalidBetStatus
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// CHECK: Treasury PDA for holding bet funds (must exist from create_bet)
+    #[account(
+        mut,
+        seeds = [b"bet-treasury-", bet.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Treasury account
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Fee vault PDA receiving the protocol fee
+    #[account(
+        mut,
+        seeds = [b"fee-vault"],
+        bump = config.fee_vault_bump
+    )]
+    pub fee_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // SPL bets only - leave these out for SOL bets
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub fee_vault_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+// Resolve a closed pool bet and settle the creator's side. Each fill is then paid its own
+// share of the treasury with claim_fill - winning fills get their stake back plus the slice
+// of the creator's stake they matched. Once resolve_by passes anyone can void the pool instead.
+pub fn resolve_pool_bet(ctx: Context<ResolvePoolBet>, outcome: u8) -> Result<()> {
+    // Validate outcome - Pending is not a valid resolution
+    let outcome = BetOutcome::try_from(outcome)?;
+    require!(
+        outcome != BetOutcome::Pending,
+        crate::error::BetError::InvalidOutcome
+    );
+
+    let clock = Clock::get()?;
+    let refund = clock.unix_timestamp >= ctx.accounts.bet.resolve_by;
+    if refund {
+        // Once the resolution deadline passes the pool can only be refunded
+        require!(
+            outcome == BetOutcome::Void,
+            crate::error::BetError::ResolutionDeadlinePassed
+        );
+    } else {
+        check_referee(
+            ctx.accounts.bet.referee_type,
+            &ctx.accounts.bet.referee,
+            &ctx.accounts.resolver.key(),
+            &ctx.accounts.referee.key(),
+            &ctx.accounts.creator.key(),
+        )?;
+    }
+
+    let treasury = BetTreasury::new(
+        &ctx.accounts.bet,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+    let creator = ctx.accounts.creator.to_account_info();
+    let creator_token_account = ctx.accounts.creator_token_account.as_ref().map(|account| account.to_account_info());
+
+    let bet = &mut ctx.accounts.bet;
+    let creator_profile = &mut ctx.accounts.creator_profile;
+
+    bet.outcome = outcome as u8;
+    bet.status = if refund { BetStatus::Voided as u8 } else { BetStatus::Resolved as u8 };
+    bet.resolved_at = Some(clock.unix_timestamp);
+    // Fills are charged the same rate when they claim, even if the config changes in between
+    bet.protocol_fee_bps = ctx.accounts.config.fee_bps;
+    // The creator's matched stake, for the profile stats. It's no longer locked either way -
+    // fills are released one by one as they claim
+    let creator_staked = bet.creator_deposit;
+    creator_profile.active_bet_count = creator_profile.active_bet_count.saturating_sub(1);
+    creator_profile.unlock_stake(PositionSide::Creator, creator_staked);
+
+    match outcome {
+        BetOutcome::CreatorWins => {
+            bet.winner = Some(bet.creator);
+
+            // Creator takes every fill's stake, less the protocol fee on the whole pot
+            let stakes = bet.deposits()?;
+            let protocol_fee = bps_of(stakes, bet.protocol_fee_bps as u64)?;
+            let winnings = stakes
+                .checked_sub(protocol_fee)
+                .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+            bet.creator_deposit = 0;
+            bet.acceptor_deposit = 0;
+
+            treasury.pay(
+                &ctx.accounts.fee_vault.to_account_info(),
+                ctx.accounts.fee_vault_token_account.as_ref().map(|account| account.to_account_info()),
+                protocol_fee,
+            )?;
+            bet.protocol_fee = protocol_fee;
+            treasury.pay(&creator, creator_token_account.clone(), winnings)?;
+            treasury.sweep_excess(bet, &creator, creator_token_account)?;
+
+            creator_profile.record_result(PositionSide::Creator, BetResult::Win, creator_staked, winnings)?;
+        }
+        BetOutcome::AcceptorWins => {
+            // Every fill wins, so there is no single winner. The matched stake stays in the treasury for claim_fill
+            bet.winner = None;
+
+            creator_profile.record_result(PositionSide::Creator, BetResult::Loss, creator_staked, 0)?;
+        }
+        _ => {
+            // Push or Void - the creator gets back their matched stake, fills reclaim theirs with claim_fill
+            bet.winner = None;
+            let creator_refund = bet.creator_deposit;
+            bet.creator_deposit = 0;
+            treasury.pay(&creator, creator_token_account.clone(), creator_refund)?;
+            treasury.sweep_excess(bet, &creator, creator_token_account)?;
+
+            // Refunds past resolve_by aren't counted, same as refund_unresolved_bet
+            if outcome == BetOutcome::Push {
+                creator_profile.record_result(PositionSide::Creator, BetResult::Push, creator_staked, creator_refund)?;
+            } else if !refund {
+                creator_profile.record_result(PositionSide::Creator, BetResult::Void, creator_staked, creator_refund)?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::state::profile::Profile;
+
+#[derive(Accounts)]
+pub struct SetMaxExposure<'info> {
+    pub wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"username-", profile.name.as_ref()],
+        bump = profile.bump,
+        constraint = profile.wallet == wallet.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub profile: Account<'info, Profile>,
+}
+
+// Cap how much the user can have locked in bets at once (None removes the cap). Only checked
+// when a new stake is locked, so lowering it below the current exposure doesn't touch open bets
+pub fn set_max_exposure(ctx: Context<SetMaxExposure>, max_exposure: Option<u64>) -> Result<()> {
+    ctx.accounts.profile.max_exposure = max_exposure;
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::bet::{BetStatus, RefereeType, BetAvailableTo};
+use crate::state::market::{Market, MarketStake};
+use crate::treasury::BetTreasury;
+
+#[derive(Accounts)]
+pub struct StakeMarket<'info> {
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = market.status == BetStatus::Open as u8 @ crate::error::BetError::InvalidBetStatus
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = staker,
+        space = MarketStake::LEN,
+        seeds = [b"market-stake", market.key().as_ref(), &market.stake_count.to_le_bytes()],
+        bump
+    )]
+    pub stake: Account<'info, MarketStake>,
+
+    /// CHECK: Treasury PDA holding the market's stakes
+    #[account(
+        mut,
+        seeds = [b"market-treasury-", market.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Treasury account
+    pub treasury: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // SPL markets only - leave these out for SOL markets
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub staker_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+// Stake `amount` on one outcome of an open market. Each call makes a new stake account,
+// so a wallet can back several outcomes or add to one later.
+pub fn stake_market(ctx: Context<StakeMarket>, outcome: u8, amount: u64) -> Result<()> {
+    let treasury = BetTreasury::for_market(
+        &ctx.accounts.market,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+
+    let market = &mut ctx.accounts.market;
+    let staker = ctx.accounts.staker.key();
+    let clock = Clock::get()?;
+
+    require!(
+        market.expires_at > clock.unix_timestamp,
+        crate::error::BetError::BetExpired
+    );
+    require!(
+        outcome < market.outcome_count,
+        crate::error::BetError::InvalidMarketOutcome
+    );
+    require!(
+        amount > 0,
+        crate::error::BetError::InvalidStakeAmount
+    );
+
+    // A designated referee can't have money on the outcome they decide
+    require!(
+        market.referee_type == RefereeType::HonorSystem as u8 || staker != market.referee,
+        crate::error::BetError::Unauthorized
+    );
+
+    // Private markets are between the creator and their recipient
+    if market.bet_available_to == BetAvailableTo::Private as u8 {
+        require!(
+            staker == market.creator || market.private_bet_recipient == Some(staker),
+            crate::error::BetError::Unauthorized
+        );
+    }
+
+    treasury.deposit(
+        &ctx.accounts.staker.to_account_info(),
+        ctx.accounts.staker_token_account.as_ref().map(|account| account.to_account_info()),
+        amount,
+    )?;
+
+    let stake = &mut ctx.accounts.stake;
+    stake.market = market.key();
+    stake.staker = staker;
+    stake.outcome = outcome;
+    stake.amount = amount;
+    stake.created_at = clock.unix_timestamp;
+    stake.version = 1;
+    stake.bump = ctx.bumps.stake;
+    stake._padding = [0; 5];
+
+    let pool = &mut market.outcome_pools[outcome as usize];
+    *pool = pool
+        .checked_add(amount)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+    market.total_pool = market.total_pool
+        .checked_add(amount)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+    market.stake_count = market.stake_count
+        .checked_add(1)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+    market.open_stake_count = market.open_stake_count
+        .checked_add(1)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::bet::{Bet, BetStatus, PositionSide};
+
+#[derive(Accounts)]
+pub struct TransferPosition<'info> {
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = !bet.partial_fills @ crate::error::BetError::PoolBet,
+        constraint = bet.status == BetStatus::Accepted as u8
+            || bet.status == BetStatus::PendingFinalization as u8
+            || bet.status == BetStatus::Disputed as u8 @ crate::error::BetError::InvalidBetStatus
+    )]
+    pub bet: Account<'info, Bet>,
+}
+
+// Hand one side of an accepted bet to another wallet, e.g. after selling it. The new holder
+// is paid when the bet settles and can negotiate an early settlement; proposing outcomes,
+// disputes and profile stats stay with the original creator and acceptor.
+pub fn transfer_position(ctx: Context<TransferPosition>, side: u8, new_holder: Pubkey) -> Result<()> {
+    let bet = &mut ctx.accounts.bet;
+    let holder = ctx.accounts.holder.key();
+
+    require!(
+        new_holder != Pubkey::default(),
+        crate::error::BetError::InvalidPositionHolder
+    );
+
+    if side == PositionSide::Creator as u8 {
+        require!(
+            holder == bet.creator_position_holder,
+            crate::error::BetError::Unauthorized
+        );
+        bet.creator_position_holder = new_holder;
+    } else if side == PositionSide::Acceptor as u8 {
+        require!(
+            Some(holder) == bet.acceptor_position_holder,
+            crate::error::BetError::Unauthorized
+        );
+        bet.acceptor_position_holder = Some(new_holder);
+    } else {
+        return err!(crate::error::BetError::InvalidPositionHolder);
+    }
+
+    // A pending early settlement was offered to (or by) the previous holder
+    bet.settlement_proposer = None;
+    bet.settlement_creator_amount = 0;
+
+    Ok(())
+}
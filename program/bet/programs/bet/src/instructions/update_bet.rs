@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::math::{canonical_odds, stake_at_odds};
+use crate::state::bet::{Bet, BetStatus, BetAvailableTo, OddsTerms, PositionSide, RefereeType};
+use crate::state::profile::Profile;
+
+#[derive(Accounts)]
+pub struct UpdateBet<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"username-", profile.name.as_ref()],
+        bump = profile.bump,
+        constraint = profile.wallet == creator.key() @ crate::error::BetError::InvalidProfileOwner
+    )]
+    pub profile: Account<'info, Profile>,
+    
+    #[account(
+        mut,
+        constraint = bet.creator == creator.key() @ crate::error::BetError::Unauthorized,
+        constraint = bet.status == BetStatus::Open as u8 @ crate::error::BetError::InvalidBetStatus
+    )]
+    pub bet: Account<'info, Bet>,
+    
+    /// CHECK: Treasury PDA for holding bet funds (must exist from create_bet)
+    #[account(
+        mut,
+        seeds = [b"bet-treasury-", bet.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Treasury account
+    pub treasury: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    // SPL bets only - leave these out for SOL bets
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(mut)]
+    pub vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(mut)]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+// Replaces the editable terms of an open bet - pass the current value for anything that isn't changing
+pub fn update_bet(
+    ctx: Context<UpdateBet>,
+    bet_amount: u64,
+    description: [u8; 128],
+    odds: OddsTerms,
+    expires_at: i64,
+    bet_available_to: u8,
+    private_bet_recipient: Option<Pubkey>,
+) -> Result<()> {
+    let treasury = crate::treasury::BetTreasury::new(
+        &ctx.accounts.bet,
+        ctx.accounts.treasury.to_account_info(),
+        ctx.bumps.treasury,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.mint.as_deref(),
+        ctx.accounts.vault.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+    let creator_token_account = ctx.accounts.creator_token_account.as_ref().map(|account| account.to_account_info());
+    
+    let bet = &mut ctx.accounts.bet;
+    let clock = Clock::get()?;
+    
+    // Can only edit a bet nobody has taken or filled yet (status check already ensures it's Open)
+    require!(
+        bet.acceptor.is_none() && bet.filled_amount == 0,
+        crate::error::BetError::BetAlreadyAccepted
+    );
+    
+    let (odds_win, odds_lose) = canonical_odds(&odds)?;
+    stake_at_odds(bet_amount, odds_win, odds_lose)?;
+    
+    // Parlay odds were checked against the legs at creation, keep them
+    if bet.referee_type == RefereeType::Parlay as u8 {
+        require!(
+            odds_win == bet.odds_win && odds_lose == bet.odds_lose,
+            crate::error::BetError::InvalidParlayOdds
+        );
+    }
+    
+    // New expiry can't already have passed, and the referee still needs time to resolve after it
+    require!(
+        expires_at > clock.unix_timestamp,
+        crate::error::BetError::InvalidExpiration
+    );
+    require!(
+        bet.resolve_by >= expires_at,
+        crate::error::BetError::InvalidResolutionDeadline
+    );
+    if bet.referee_type == RefereeType::Oracle as u8 {
+        require!(
+            bet.oracle.observation_time >= expires_at,
+            crate::error::BetError::InvalidOracleTerms
+        );
+    }
+    
+    // Private bets need someone to be private with
+    require!(
+        bet_available_to == BetAvailableTo::Public as u8
+            || bet_available_to == BetAvailableTo::FriendsOnly as u8
+            || (bet_available_to == BetAvailableTo::Private as u8 && private_bet_recipient.is_some()),
+        crate::error::BetError::InvalidBetAvailableTo
+    );
+    
+    let previous_amount = bet.creator_deposit;
+    
+    bet.bet_amount = bet_amount;
+    bet.description = description;
+    bet.odds_win = odds_win;
+    bet.odds_lose = odds_lose;
+    bet.expires_at = expires_at;
+    bet.bet_available_to = bet_available_to;
+    bet.private_bet_recipient = if bet_available_to == BetAvailableTo::Private as u8 {
+        private_bet_recipient
+    } else {
+        None
+    };
+    bet.terms_revision = bet.terms_revision
+        .checked_add(1)
+        .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+    
+    // A flat referee fee can't be more than the pot it is paid from
+    require!(
+        bet.referee_fee_amount()? <= bet.pot()?,
+        crate::error::BetError::InvalidRefereeFee
+    );
+    
+    // Top up the treasury or hand back the difference so it always holds exactly the creator's stake
+    if bet_amount > previous_amount {
+        treasury.deposit(
+            &ctx.accounts.creator.to_account_info(),
+            creator_token_account,
+            bet_amount - previous_amount,
+        )?;
+    } else if bet_amount < previous_amount {
+        treasury.pay(
+            &ctx.accounts.creator.to_account_info(),
+            creator_token_account,
+            previous_amount - bet_amount,
+        )?;
+    }
+    bet.creator_deposit = bet_amount;
+    
+    // Only raising the stake is checked against max_exposure
+    let profile = &mut ctx.accounts.profile;
+    if bet_amount > previous_amount {
+        profile.lock_stake(PositionSide::Creator, bet_amount - previous_amount)?;
+    } else {
+        profile.unlock_stake(PositionSide::Creator, previous_amount - bet_amount);
+    }
+    
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_PROTOCOL_FEE_BPS;
+use crate::state::config::Config;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ crate::error::BetError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn update_config(
+    ctx: Context<UpdateConfig>,
+    fee_bps: u16,
+    fee_recipient: Pubkey,
+    new_authority: Pubkey,
+) -> Result<()> {
+    require!(
+        fee_bps <= MAX_PROTOCOL_FEE_BPS,
+        crate::error::BetError::InvalidProtocolFee
+    );
+    
+    // Only affects bets settled from now on, settled bets keep the fee recorded on them
+    let config = &mut ctx.accounts.config;
+    config.fee_bps = fee_bps;
+    config.fee_recipient = fee_recipient;
+    config.authority = new_authority;
+    
+    Ok(())
+}
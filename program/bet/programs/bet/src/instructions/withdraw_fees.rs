@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::config::Config;
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ crate::error::BetError::Unauthorized,
+        has_one = fee_recipient @ crate::error::BetError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// CHECK: Fee vault PDA holding protocol fees
+    #[account(
+        mut,
+        seeds = [b"fee-vault"],
+        bump = config.fee_vault_bump
+    )]
+    pub fee_vault: UncheckedAccount<'info>,
+    
+    /// CHECK: Fee recipient is validated by checking config.fee_recipient matches this account (mut for SOL transfer)
+    #[account(mut)]
+    pub fee_recipient: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    // SPL fees only - leave these out to withdraw SOL fees
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = fee_vault,
+        associated_token::token_program = token_program
+    )]
+    pub fee_vault_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = fee_recipient,
+        token::token_program = token_program
+    )]
+    pub fee_recipient_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+    // Prepare seeds for PDA signing
+    let seeds = &[
+        b"fee-vault".as_ref(),
+        &[ctx.accounts.config.fee_vault_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    
+    if let (Some(mint), Some(fee_vault_token_account), Some(fee_recipient_token_account), Some(token_program)) = (
+        &ctx.accounts.mint,
+        &ctx.accounts.fee_vault_token_account,
+        &ctx.accounts.fee_recipient_token_account,
+        &ctx.accounts.token_program,
+    ) {
+        require!(
+            amount <= fee_vault_token_account.amount,
+            crate::error::BetError::InsufficientFees
+        );
+        
+        return token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                TransferChecked {
+                    from: fee_vault_token_account.to_account_info(),
+                    mint: mint.to_account_info(),
+                    to: fee_recipient_token_account.to_account_info(),
+                    authority: ctx.accounts.fee_vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            mint.decimals,
+        );
+    }
+    
+    // SOL fees - the vault keeps its rent-exempt minimum
+    let available = ctx.accounts.fee_vault.lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    require!(
+        amount <= available,
+        crate::error::BetError::InsufficientFees
+    );
+    
+    anchor_lang::solana_program::program::invoke_signed(
+        &anchor_lang::solana_program::system_instruction::transfer(
+            ctx.accounts.fee_vault.key,
+            ctx.accounts.fee_recipient.key,
+            amount,
+        ),
+        &[
+            ctx.accounts.fee_vault.to_account_info(),
+            ctx.accounts.fee_recipient.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        signer_seeds,
+    )?;
+    
+    Ok(())
+}
@@ -4,8 +4,14 @@ pub mod state;
 pub mod instructions;
 pub mod error;
 pub mod constants;
+pub mod treasury;
+pub mod oracle;
+pub mod math;
 
 pub use state::*;
+// Some handlers share a name with a module of `instructions`; #[program] only needs the
+// accounts structs and their generated client modules from here
+#[allow(ambiguous_glob_reexports)]
 pub use instructions::*;
 pub use error::*;
 
@@ -19,17 +25,22 @@ pub mod bet {
         instructions::create_profile(ctx, name)
     }
 
+    pub fn migrate_profile(ctx: Context<MigrateProfile>) -> Result<()> {
+        instructions::migrate_profile(ctx)
+    }
+
+    pub fn set_max_exposure(ctx: Context<SetMaxExposure>, max_exposure: Option<u64>) -> Result<()> {
+        instructions::set_max_exposure(ctx, max_exposure)
+    }
+
     pub fn create_bet(
         ctx: Context<CreateBet>,
         bet_amount: u64,
         description: [u8; 128],
         referee_type: u8,
         category: u8,
-        odds_win: u64,
-        odds_lose: u64,
-        expires_at: i64,
-        bet_available_to: u8,
-        private_bet_recipient: Option<Pubkey>,
+        odds: OddsTerms,
+        terms: BetTerms,
     ) -> Result<()> {
         instructions::create_bet(
             ctx,
@@ -37,27 +48,32 @@ pub mod bet {
             description,
             referee_type,
             category,
-            odds_win,
-            odds_lose,
-            expires_at,
-            bet_available_to,
-            private_bet_recipient,
+            odds,
+            terms,
         )
     }
 
+    pub fn migrate_bet(ctx: Context<MigrateBet>) -> Result<()> {
+        instructions::migrate_bet(ctx)
+    }
+
     pub fn cancel_bet(ctx: Context<CancelBet>) -> Result<()> {
         instructions::cancel_bet(ctx)
     }
 
-    pub fn accept_bet(ctx: Context<AcceptBet>) -> Result<()> {
-        instructions::accept_bet(ctx)
+    pub fn accept_bet(
+        ctx: Context<AcceptBet>,
+        expected_acceptor_bet_amount: u64,
+        expected_terms_revision: u32,
+    ) -> Result<()> {
+        instructions::accept_bet(ctx, expected_acceptor_bet_amount, expected_terms_revision)
     }
 
     pub fn resolve_bet(
         ctx: Context<ResolveBet>,
-        winner_is_creator: bool,
+        outcome: u8,
     ) -> Result<()> {
-        instructions::resolve_bet(ctx, winner_is_creator)
+        instructions::resolve_bet(ctx, outcome)
     }
 
     pub fn delete_bet(ctx: Context<DeleteBet>) -> Result<()> {
@@ -71,4 +87,186 @@ pub mod bet {
     pub fn accept_friend(ctx: Context<AcceptFriend>) -> Result<()> {
         instructions::accept_friend(ctx)
     }
+
+    pub fn reclaim_expired_bet(ctx: Context<ReclaimExpiredBet>) -> Result<()> {
+        instructions::reclaim_expired_bet(ctx)
+    }
+
+    pub fn refund_unresolved_bet(ctx: Context<RefundUnresolvedBet>) -> Result<()> {
+        instructions::refund_unresolved_bet(ctx)
+    }
+
+    pub fn propose_outcome(ctx: Context<ProposeOutcome>, outcome: u8) -> Result<()> {
+        instructions::propose_outcome(ctx, outcome)
+    }
+
+    pub fn dispute_outcome(ctx: Context<DisputeOutcome>) -> Result<()> {
+        instructions::dispute_outcome(ctx)
+    }
+
+    pub fn finalize_bet(ctx: Context<FinalizeBet>) -> Result<()> {
+        instructions::finalize_bet(ctx)
+    }
+
+    pub fn settle_with_oracle(ctx: Context<SettleWithOracle>) -> Result<()> {
+        instructions::settle_with_oracle(ctx)
+    }
+
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        fee_bps: u16,
+        fee_recipient: Pubkey,
+    ) -> Result<()> {
+        instructions::initialize_config(ctx, fee_bps, fee_recipient)
+    }
+
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        fee_bps: u16,
+        fee_recipient: Pubkey,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::update_config(ctx, fee_bps, fee_recipient, new_authority)
+    }
+
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        instructions::withdraw_fees(ctx, amount)
+    }
+
+    pub fn accept_referee_role(ctx: Context<AcceptRefereeRole>) -> Result<()> {
+        instructions::accept_referee_role(ctx)
+    }
+
+    pub fn decline_referee_role(ctx: Context<DeclineRefereeRole>) -> Result<()> {
+        instructions::decline_referee_role(ctx)
+    }
+
+    pub fn update_bet(
+        ctx: Context<UpdateBet>,
+        bet_amount: u64,
+        description: [u8; 128],
+        odds: OddsTerms,
+        expires_at: i64,
+        bet_available_to: u8,
+        private_bet_recipient: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::update_bet(
+            ctx,
+            bet_amount,
+            description,
+            odds,
+            expires_at,
+            bet_available_to,
+            private_bet_recipient,
+        )
+    }
+
+    pub fn create_counter_offer(
+        ctx: Context<CreateCounterOffer>,
+        bet_amount: u64,
+        odds: OddsTerms,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::create_counter_offer(ctx, bet_amount, odds, expires_at)
+    }
+
+    pub fn accept_counter_offer(ctx: Context<AcceptCounterOffer>) -> Result<()> {
+        instructions::accept_counter_offer(ctx)
+    }
+
+    pub fn close_counter_offer(ctx: Context<CloseCounterOffer>) -> Result<()> {
+        instructions::close_counter_offer(ctx)
+    }
+
+    pub fn propose_settlement(ctx: Context<ProposeSettlement>, creator_amount: u64) -> Result<()> {
+        instructions::propose_settlement(ctx, creator_amount)
+    }
+
+    pub fn accept_settlement(ctx: Context<AcceptSettlement>, expected_creator_amount: u64) -> Result<()> {
+        instructions::accept_settlement(ctx, expected_creator_amount)
+    }
+
+    pub fn transfer_position(ctx: Context<TransferPosition>, side: u8, new_holder: Pubkey) -> Result<()> {
+        instructions::transfer_position(ctx, side, new_holder)
+    }
+
+    pub fn fill_bet(
+        ctx: Context<FillBet>,
+        creator_amount: u64,
+        expected_terms_revision: u32,
+    ) -> Result<()> {
+        instructions::fill_bet(ctx, creator_amount, expected_terms_revision)
+    }
+
+    pub fn resolve_pool_bet(ctx: Context<ResolvePoolBet>, outcome: u8) -> Result<()> {
+        instructions::resolve_pool_bet(ctx, outcome)
+    }
+
+    pub fn claim_fill(ctx: Context<ClaimFill>) -> Result<()> {
+        instructions::claim_fill(ctx)
+    }
+
+    pub fn create_market(
+        ctx: Context<CreateMarket>,
+        description: [u8; 128],
+        category: u8,
+        referee_type: u8,
+        outcome_labels: Vec<[u8; 32]>,
+        terms: MarketTerms,
+    ) -> Result<()> {
+        instructions::create_market(
+            ctx,
+            description,
+            category,
+            referee_type,
+            outcome_labels,
+            terms,
+        )
+    }
+
+    pub fn stake_market(ctx: Context<StakeMarket>, outcome: u8, amount: u64) -> Result<()> {
+        instructions::stake_market(ctx, outcome, amount)
+    }
+
+    pub fn resolve_market(ctx: Context<ResolveMarket>, winning_outcome: Option<u8>) -> Result<()> {
+        instructions::resolve_market(ctx, winning_outcome)
+    }
+
+    pub fn claim_market_stake(ctx: Context<ClaimMarketStake>) -> Result<()> {
+        instructions::claim_market_stake(ctx)
+    }
+
+    pub fn delete_market(ctx: Context<DeleteMarket>) -> Result<()> {
+        instructions::delete_market(ctx)
+    }
+
+    pub fn settle_parlay(ctx: Context<SettleParlay>) -> Result<()> {
+        instructions::settle_parlay(ctx)
+    }
+
+    pub fn cast_referee_vote(ctx: Context<CastRefereeVote>, outcome: u8) -> Result<()> {
+        instructions::cast_referee_vote(ctx, outcome)
+    }
+
+    #[cfg(feature = "mock-oracle")]
+    pub fn init_mock_price_feed(
+        ctx: Context<InitMockPriceFeed>,
+        price: i64,
+        conf: u64,
+        expo: i32,
+        publish_time: i64,
+    ) -> Result<()> {
+        instructions::init_mock_price_feed(ctx, price, conf, expo, publish_time)
+    }
+
+    #[cfg(feature = "mock-oracle")]
+    pub fn set_mock_price(
+        ctx: Context<SetMockPrice>,
+        price: i64,
+        conf: u64,
+        expo: i32,
+        publish_time: i64,
+    ) -> Result<()> {
+        instructions::set_mock_price(ctx, price, conf, expo, publish_time)
+    }
 }
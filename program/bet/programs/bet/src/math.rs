@@ -0,0 +1,192 @@
+use anchor_lang::prelude::*;
+use crate::constants::BPS_DENOMINATOR;
+use crate::error::BetError;
+use crate::state::bet::{OddsFormat, OddsTerms};
+
+// Settlement math shared by every instruction. Products are taken in u128 so large stakes
+// can't overflow half way, and results are checked back into u64.
+//
+// Rounding rules:
+// - Every division rounds down.
+// - A stake derived from odds rounds down, so nobody puts up more than the quoted odds ask
+//   for. Terms whose stake rounds down to zero are rejected.
+// - Fees (protocol, referee, dispute bond) round down, in favour of the parties.
+// - Pro-rata shares round down. The remainder (dust) stays in the treasury and goes to whoever
+//   is paid what is left: the winner of a decided bet, the creator of a pushed or voided bet,
+//   the creator of a market when it is deleted.
+
+// amount * numerator / denominator, rounded down
+pub fn mul_div(amount: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    let result = (amount as u128)
+        .checked_mul(numerator as u128)
+        .and_then(|x| x.checked_div(denominator as u128))
+        .ok_or(BetError::ArithmeticOverflow)?;
+    u64::try_from(result).map_err(|_| BetError::ArithmeticOverflow.into())
+}
+
+// Stake that matches `amount` at odds_win:odds_lose (the acceptor's side of the creator's stake)
+pub fn stake_at_odds(amount: u64, odds_win: u64, odds_lose: u64) -> Result<u64> {
+    let stake = mul_div(amount, odds_win, odds_lose)?;
+    require!(stake > 0, BetError::StakeRoundsToZero);
+    Ok(stake)
+}
+
+// Basis points of an amount, rounded down
+pub fn bps_of(amount: u64, bps: u64) -> Result<u64> {
+    mul_div(amount, bps, BPS_DENOMINATOR)
+}
+
+// `share` out of `whole` of `total`, rounded down
+pub fn pro_rata(total: u64, share: u64, whole: u64) -> Result<u64> {
+    mul_div(total, share, whole)
+}
+
+// Convert odds as quoted into the stored odds_win:odds_lose form, in lowest terms.
+// odds_win:odds_lose is what the acceptor stakes against the creator's stake, i.e. the
+// creator's fractional odds.
+pub fn canonical_odds(terms: &OddsTerms) -> Result<(u64, u64)> {
+    let (odds_win, odds_lose) = match OddsFormat::try_from(terms.format)? {
+        // 5/2: win 5 for every 2 staked
+        OddsFormat::Fractional => {
+            require!(
+                terms.numerator > 0 && terms.denominator > 0,
+                BetError::InvalidOdds
+            );
+            (terms.numerator as u64, terms.denominator)
+        }
+        // 3.5 as 35/10: returns 3.5 per 1 staked, stake included
+        OddsFormat::Decimal => {
+            require!(
+                terms.denominator > 0 && terms.numerator > 0 && terms.numerator as u64 > terms.denominator,
+                BetError::InvalidOdds
+            );
+            (terms.numerator as u64 - terms.denominator, terms.denominator)
+        }
+        // +150 wins 150 per 100 staked, -200 stakes 200 to win 100 (denominator unused)
+        OddsFormat::American => {
+            let line = terms.numerator.unsigned_abs();
+            require!(line >= 100, BetError::InvalidOdds);
+            if terms.numerator > 0 {
+                (line, 100)
+            } else {
+                (100, line)
+            }
+        }
+    };
+
+    let divisor = gcd(odds_win, odds_lose);
+    Ok((odds_win / divisor, odds_lose / divisor))
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn odds(format: OddsFormat, numerator: i64, denominator: u64) -> OddsTerms {
+        OddsTerms { format: format as u8, numerator, denominator }
+    }
+
+    fn err(error: BetError) -> Error {
+        error.into()
+    }
+
+    #[test]
+    fn mul_div_rounds_down() {
+        assert_eq!(mul_div(10, 1, 3).unwrap(), 3);
+        assert_eq!(mul_div(10, 2, 3).unwrap(), 6);
+        assert_eq!(mul_div(7, 3, 7).unwrap(), 3);
+    }
+
+    #[test]
+    fn mul_div_takes_the_product_in_u128() {
+        assert_eq!(mul_div(u64::MAX, u64::MAX, u64::MAX).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn mul_div_overflow_is_an_error() {
+        assert_eq!(mul_div(u64::MAX, 2, 1).unwrap_err(), err(BetError::ArithmeticOverflow));
+        assert_eq!(mul_div(1, 1, 0).unwrap_err(), err(BetError::ArithmeticOverflow));
+    }
+
+    #[test]
+    fn stake_at_odds_rounds_down_and_rejects_zero() {
+        // 0.1 SOL at 5:2
+        assert_eq!(stake_at_odds(100_000_000, 5, 2).unwrap(), 250_000_000);
+        // 3 at 1:2 is 1.5, the acceptor puts up 1
+        assert_eq!(stake_at_odds(3, 1, 2).unwrap(), 1);
+        assert_eq!(stake_at_odds(1, 1, 2).unwrap_err(), err(BetError::StakeRoundsToZero));
+        assert_eq!(stake_at_odds(u64::MAX, 3, 1).unwrap_err(), err(BetError::ArithmeticOverflow));
+    }
+
+    #[test]
+    fn bps_of_rounds_down_in_favour_of_the_parties() {
+        assert_eq!(bps_of(1_000_000, 250).unwrap(), 25_000);
+        assert_eq!(bps_of(199, 50).unwrap(), 0);
+        assert_eq!(bps_of(10_001, 1).unwrap(), 1);
+        assert_eq!(bps_of(u64::MAX, BPS_DENOMINATOR).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn pro_rata_shares_never_add_up_to_more_than_the_total() {
+        let shares = [pro_rata(100, 1, 3).unwrap(), pro_rata(100, 1, 3).unwrap(), pro_rata(100, 1, 3).unwrap()];
+        assert_eq!(shares, [33, 33, 33]);
+        assert!(shares.iter().sum::<u64>() <= 100);
+        assert_eq!(pro_rata(100, 3, 3).unwrap(), 100);
+        assert_eq!(pro_rata(100, 1, 0).unwrap_err(), err(BetError::ArithmeticOverflow));
+    }
+
+    #[test]
+    fn every_odds_format_gives_the_same_canonical_odds() {
+        // 5/2 = 3.5 decimal = +250
+        let expected = (5, 2);
+        assert_eq!(canonical_odds(&odds(OddsFormat::Fractional, 10, 4)).unwrap(), expected);
+        assert_eq!(canonical_odds(&odds(OddsFormat::Decimal, 35, 10)).unwrap(), expected);
+        assert_eq!(canonical_odds(&odds(OddsFormat::American, 250, 0)).unwrap(), expected);
+
+        // 1/2 = 1.5 decimal = -200
+        let expected = (1, 2);
+        assert_eq!(canonical_odds(&odds(OddsFormat::Fractional, 1, 2)).unwrap(), expected);
+        assert_eq!(canonical_odds(&odds(OddsFormat::Decimal, 150, 100)).unwrap(), expected);
+        assert_eq!(canonical_odds(&odds(OddsFormat::American, -200, 0)).unwrap(), expected);
+
+        // Evens: 1/1 = 2.0 decimal = +100 = -100
+        let expected = (1, 1);
+        assert_eq!(canonical_odds(&odds(OddsFormat::Fractional, 7, 7)).unwrap(), expected);
+        assert_eq!(canonical_odds(&odds(OddsFormat::Decimal, 2, 1)).unwrap(), expected);
+        assert_eq!(canonical_odds(&odds(OddsFormat::American, 100, 0)).unwrap(), expected);
+        assert_eq!(canonical_odds(&odds(OddsFormat::American, -100, 0)).unwrap(), expected);
+    }
+
+    #[test]
+    fn american_lines_under_100_are_rejected() {
+        for line in [0, 1, 99, -1, -99] {
+            assert_eq!(
+                canonical_odds(&odds(OddsFormat::American, line, 0)).unwrap_err(),
+                err(BetError::InvalidOdds)
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_fractional_and_decimal_odds_are_rejected() {
+        for terms in [
+            odds(OddsFormat::Fractional, 0, 1),
+            odds(OddsFormat::Fractional, -5, 2),
+            odds(OddsFormat::Fractional, 5, 0),
+            // Decimal odds return the stake, so they have to be above 1
+            odds(OddsFormat::Decimal, 10, 10),
+            odds(OddsFormat::Decimal, 5, 10),
+            odds(OddsFormat::Decimal, 35, 0),
+        ] {
+            assert_eq!(canonical_odds(&terms).unwrap_err(), err(BetError::InvalidOdds));
+        }
+        assert!(canonical_odds(&OddsTerms { format: 3, numerator: 5, denominator: 2 }).is_err());
+    }
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use super::{PriceFeedAdapter, PriceObservation};
+
+// Mock price feed layout for tests, only compiled with the `mock-oracle` feature.
+// Written through init_mock_price_feed / set_mock_price, owned by this program.
+#[account]
+#[repr(C)]
+pub struct MockPriceFeed {
+    pub authority: Pubkey,                  // Wallet allowed to update the price
+    pub price: i64,                         // Price in units of 10^expo
+    pub conf: u64,                          // Confidence interval in units of 10^expo
+    pub expo: i32,                          // Exponent of price and conf
+    pub publish_time: i64,                  // Unix timestamp the price was published at
+    pub version: u8,                        // For future upgrades
+    pub _padding: [u8; 7],                  // padding for alignment
+}
+
+impl MockPriceFeed {
+    pub const LEN: usize = 8     // discriminator
+        + 32                     // authority
+        + 8                      // price
+        + 8                      // conf
+        + 4                      // expo
+        + 8                      // publish_time
+        + 1                      // version
+        + 7;                     // padding
+}
+
+pub struct MockPriceFeedAdapter;
+
+impl PriceFeedAdapter for MockPriceFeedAdapter {
+    fn read_price(feed: &AccountInfo) -> Result<PriceObservation> {
+        // Mock feeds are only trusted if this program wrote them
+        require_keys_eq!(
+            *feed.owner,
+            crate::ID,
+            crate::error::BetError::InvalidOracleFeed
+        );
+        
+        // try_deserialize checks the discriminator
+        // Mock feeds have no feed id of their own, so the account address stands in for it
+        let feed_id = feed.key().to_bytes();
+        let data = feed.try_borrow_data()?;
+        let feed = MockPriceFeed::try_deserialize(&mut &data[..])
+            .map_err(|_| error!(crate::error::BetError::InvalidOracleFeed))?;
+        Ok(PriceObservation {
+            feed_id,
+            price: feed.price,
+            conf: feed.conf,
+            expo: feed.expo,
+            publish_time: feed.publish_time,
+        })
+    }
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::bet::{OracleFeedKind, OracleTerms};
+
+pub mod pyth;
+
+pub use pyth::*;
+
+#[cfg(feature = "mock-oracle")]
+pub mod mock;
+
+#[cfg(feature = "mock-oracle")]
+pub use mock::*;
+
+// Price read from a feed account, normalised across feed layouts
+pub struct PriceObservation {
+    pub feed_id: [u8; 32],   // Price feed the price belongs to (e.g. the Pyth feed id)
+    pub price: i64,          // Price in units of 10^expo
+    pub conf: u64,           // Confidence interval in units of 10^expo
+    pub expo: i32,           // Exponent of price and conf
+    pub publish_time: i64,   // Unix timestamp the price was published at
+}
+
+// Adapter for one price feed account layout.
+// Each supported layout gets an OracleFeedKind value and an implementation of this trait,
+// and is wired up in read_price below.
+pub trait PriceFeedAdapter {
+    // Validate the feed account (owner, layout) and read its latest price
+    fn read_price(feed: &AccountInfo) -> Result<PriceObservation>;
+}
+
+// Read the latest price from a feed account using the adapter for its layout,
+// and check it is the feed the bet's terms name
+pub fn read_price(terms: &OracleTerms, feed: &AccountInfo) -> Result<PriceObservation> {
+    let observation = match terms.feed_kind {
+        x if x == OracleFeedKind::PythPriceUpdate as u8 => PythPriceUpdateAdapter::read_price(feed)?,
+        #[cfg(feature = "mock-oracle")]
+        x if x == OracleFeedKind::Mock as u8 => MockPriceFeedAdapter::read_price(feed)?,
+        _ => return err!(crate::error::BetError::InvalidOracleFeed),
+    };
+    require!(
+        observation.feed_id == terms.feed_id,
+        crate::error::BetError::InvalidOracleFeed
+    );
+    Ok(observation)
+}
+
+// Compare a price against a threshold expressed with a different exponent.
+// Both values are scaled to the smaller exponent so no precision is lost.
+pub fn compare_price(price: i64, price_expo: i32, threshold: i64, threshold_expo: i32) -> Result<std::cmp::Ordering> {
+    let scale = |value: i64, expo: i32| -> Result<i128> {
+        let shift = expo
+            .checked_sub(price_expo.min(threshold_expo))
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+        10i128
+            .checked_pow(shift as u32)
+            .and_then(|factor| (value as i128).checked_mul(factor))
+            .ok_or(crate::error::BetError::ArithmeticOverflow.into())
+    };
+    Ok(scale(price, price_expo)?.cmp(&scale(threshold, threshold_expo)?))
+}
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use super::{PriceFeedAdapter, PriceObservation};
+
+// Pyth Solana Receiver program - owns every PriceUpdateV2 account, including the sponsored push feeds
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+// Anchor discriminator of the receiver's PriceUpdateV2 account (sha256("account:PriceUpdateV2")[..8])
+const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+// VerificationLevel::Full borsh tag - every Wormhole guardian signature was checked
+const VERIFICATION_LEVEL_FULL: u8 = 1;
+
+// PriceUpdateV2 layout: discriminator, write_authority, verification_level, then the price message.
+// Only fully verified updates are read, so the verification level is the one-byte Full tag
+// and the message starts at a fixed offset.
+const VERIFICATION_LEVEL_OFFSET: usize = 8 + 32;
+const MESSAGE_OFFSET: usize = VERIFICATION_LEVEL_OFFSET + 1;
+const MESSAGE_LEN: usize = 32 // feed_id
+    + 8                      // price
+    + 8                      // conf
+    + 4                      // exponent
+    + 8;                     // publish_time (prev_publish_time, ema_price, ema_conf and posted_slot follow, unused)
+
+pub struct PythPriceUpdateAdapter;
+
+impl PythPriceUpdateAdapter {
+    // Read a price from PriceUpdateV2 account data (owner is checked by the caller)
+    pub fn parse(data: &[u8]) -> Result<PriceObservation> {
+        require!(
+            data.len() >= MESSAGE_OFFSET + MESSAGE_LEN
+                && data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR
+                && data[VERIFICATION_LEVEL_OFFSET] == VERIFICATION_LEVEL_FULL,
+            crate::error::BetError::InvalidOracleFeed
+        );
+
+        let message = &data[MESSAGE_OFFSET..MESSAGE_OFFSET + MESSAGE_LEN];
+        let mut feed_id = [0u8; 32];
+        feed_id.copy_from_slice(&message[..32]);
+        let field = |offset: usize, len: usize| &message[offset..offset + len];
+        Ok(PriceObservation {
+            feed_id,
+            price: i64::from_le_bytes(field(32, 8).try_into().unwrap()),
+            conf: u64::from_le_bytes(field(40, 8).try_into().unwrap()),
+            expo: i32::from_le_bytes(field(48, 4).try_into().unwrap()),
+            publish_time: i64::from_le_bytes(field(52, 8).try_into().unwrap()),
+        })
+    }
+}
+
+impl PriceFeedAdapter for PythPriceUpdateAdapter {
+    fn read_price(feed: &AccountInfo) -> Result<PriceObservation> {
+        // Anyone can create an account with this layout, only the receiver's are verified prices
+        require_keys_eq!(
+            *feed.owner,
+            PYTH_RECEIVER_PROGRAM_ID,
+            crate::error::BetError::InvalidOracleFeed
+        );
+
+        let data = feed.try_borrow_data()?;
+        Self::parse(&data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Fully verified SOL/USD update: $150.25 +/- $0.10 at expo -8
+    fn price_update(verification_level: &[u8]) -> Vec<u8> {
+        let mut data = PRICE_UPDATE_V2_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[7u8; 32]); // write_authority
+        data.extend_from_slice(verification_level);
+        data.extend_from_slice(&[9u8; 32]); // feed_id
+        data.extend_from_slice(&15_025_000_000i64.to_le_bytes());
+        data.extend_from_slice(&10_000_000u64.to_le_bytes());
+        data.extend_from_slice(&(-8i32).to_le_bytes());
+        data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        data.extend_from_slice(&1_699_999_999i64.to_le_bytes()); // prev_publish_time
+        data.extend_from_slice(&15_000_000_000i64.to_le_bytes()); // ema_price
+        data.extend_from_slice(&12_000_000u64.to_le_bytes()); // ema_conf
+        data.extend_from_slice(&250_000_000u64.to_le_bytes()); // posted_slot
+        data
+    }
+
+    #[test]
+    fn parses_fully_verified_update() {
+        let observation = PythPriceUpdateAdapter::parse(&price_update(&[VERIFICATION_LEVEL_FULL])).unwrap();
+        assert_eq!(observation.feed_id, [9u8; 32]);
+        assert_eq!(observation.price, 15_025_000_000);
+        assert_eq!(observation.conf, 10_000_000);
+        assert_eq!(observation.expo, -8);
+        assert_eq!(observation.publish_time, 1_700_000_000);
+    }
+
+    #[test]
+    fn rejects_partially_verified_update() {
+        // VerificationLevel::Partial { num_signatures: 5 }
+        assert!(PythPriceUpdateAdapter::parse(&price_update(&[0, 5])).is_err());
+    }
+
+    #[test]
+    fn rejects_other_account_layouts() {
+        let mut data = price_update(&[VERIFICATION_LEVEL_FULL]);
+        data[0] ^= 1;
+        assert!(PythPriceUpdateAdapter::parse(&data).is_err());
+        assert!(PythPriceUpdateAdapter::parse(&price_update(&[VERIFICATION_LEVEL_FULL])[..60]).is_err());
+    }
+}
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::constants::MAX_PANEL_SIZE;
+use crate::state::parlay::ParlayLeg;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum RefereeType {
@@ -6,6 +8,9 @@ pub enum RefereeType {
     Oracle = 1,
     ThirdParty = 2,
     SmartContract = 3,
+    MutualConsent = 4,    // Creator and acceptor must both propose the same outcome
+    Panel = 5,            // M of N named referees must vote for the same outcome
+    Parlay = 6,           // Derived from other bets - creator wins only if every leg goes their way
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -14,6 +19,194 @@ pub enum BetStatus {
     Accepted = 1,    // Bet accepted by another party
     Cancelled = 2,    // Bet cancelled by creator
     Resolved = 3,     // Bet resolved (winner determined)
+    Expired = 4,      // Bet expired without being accepted, stake returned to creator
+    Voided = 5,       // Bet not resolved by resolve_by, stakes returned to both parties
+    Disputed = 6,     // Outcome disputed, waiting on the dispute fallback
+    PendingFinalization = 7, // Referee recorded an outcome, challenge window is open
+    PendingReferee = 8,      // Third-party bet waiting for the referee to accept the role
+    Settled = 9,             // Both parties agreed to end the bet early and split the treasury
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BetOutcome {
+    Pending = 0,         // Bet not resolved yet
+    CreatorWins = 1,     // Creator takes the treasury
+    AcceptorWins = 2,    // Acceptor takes the treasury
+    Push = 3,            // Draw - each side gets their stake back
+    Void = 4,            // Premise became moot - each side gets their stake back
+}
+
+impl TryFrom<u8> for BetOutcome {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(BetOutcome::Pending),
+            1 => Ok(BetOutcome::CreatorWins),
+            2 => Ok(BetOutcome::AcceptorWins),
+            3 => Ok(BetOutcome::Push),
+            4 => Ok(BetOutcome::Void),
+            _ => err!(crate::error::BetError::InvalidOutcome),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DisputeFallback {
+    Refund = 0,      // Disputed bets are refunded to both parties
+    Arbiter = 1,     // Disputed bets are escalated to the arbiter named on the bet
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PositionSide {
+    Creator = 0,
+    Acceptor = 1,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RefereeFeeKind {
+    Flat = 0,        // Fixed amount in lamports (or base units of mint)
+    Bps = 1,         // Basis points of the pot
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum OddsFormat {
+    Fractional = 0,  // numerator/denominator won per unit staked, e.g. 5/2
+    Decimal = 1,     // Total return per unit staked as numerator/denominator, e.g. 3.5 = 35/10
+    American = 2,    // Moneyline in numerator, e.g. +150 or -200
+}
+
+impl TryFrom<u8> for OddsFormat {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(OddsFormat::Fractional),
+            1 => Ok(OddsFormat::Decimal),
+            2 => Ok(OddsFormat::American),
+            _ => err!(crate::error::BetError::InvalidOdds),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum OracleFeedKind {
+    Mock = 0,        // MockPriceFeed layout (only with the mock-oracle feature, for tests)
+    PythPriceUpdate = 1, // Pyth receiver PriceUpdateV2 account, fully verified updates only
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum OracleComparator {
+    Above = 0,       // Creator wins if the price is above the threshold
+    Below = 1,       // Creator wins if the price is below the threshold
+}
+
+// Terms for Oracle bets, e.g. "SOL above $200 on Friday 00:00 UTC"
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct OracleTerms {
+    pub feed_kind: u8,                      // OracleFeedKind enum value (layout of the feed account)
+    pub feed_id: [u8; 32],                  // Feed the price must come from (Pyth feed id, or the feed address for Mock)
+    pub comparator: u8,                     // OracleComparator enum value
+    pub threshold: i64,                     // Price threshold in units of 10^threshold_expo
+    pub threshold_expo: i32,                // Exponent of threshold
+    pub observation_time: i64,              // Unix timestamp the price is observed at
+    pub max_staleness: i64,                 // Max seconds between observation_time and the price's publish time
+    pub max_confidence_bps: u16,            // Max confidence interval as basis points of the price
+}
+
+impl OracleTerms {
+    pub const LEN: usize = 1     // feed_kind
+        + 32                     // feed_id
+        + 1                      // comparator
+        + 8                      // threshold
+        + 4                      // threshold_expo
+        + 8                      // observation_time
+        + 8                      // max_staleness
+        + 2;                     // max_confidence_bps
+}
+
+// Odds as passed to create_bet, update_bet and create_counter_offer - stored as odds_win:odds_lose
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct OddsTerms {
+    pub format: u8,                         // OddsFormat enum value
+    pub numerator: i64,                     // Fractional/decimal numerator, or the American moneyline
+    pub denominator: u64,                   // Fractional/decimal denominator (unused for American)
+}
+
+// Panel as passed to create_bet - members are copied into the fixed-size RefereePanel on the bet
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RefereePanelTerms {
+    pub members: Vec<Pubkey>,               // Referee wallets, at most MAX_PANEL_SIZE
+    pub threshold: u8,                      // Votes for the same outcome needed to settle (M of N)
+}
+
+// Everything create_bet takes beyond the stake, description, referee type, category and odds:
+// when and to whom the bet is offered, and the optional settlement terms
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BetTerms {
+    pub expires_at: i64,                    // Unix timestamp when the bet can no longer be accepted
+    pub resolve_by: i64,                    // Unix timestamp by which the referee must resolve the bet
+    pub bet_available_to: u8,               // BetAvailableTo enum value
+    pub private_bet_recipient: Option<Pubkey>, // Only acceptor allowed on a Private bet
+    pub dispute_fallback: u8,               // DisputeFallback enum value
+    pub arbiter: Option<Pubkey>,            // Arbiter for disputed outcomes (Arbiter fallback only)
+    pub challenge_window: i64,              // Seconds a referee's outcome can be disputed before payout (0 = pay immediately)
+    pub oracle_terms: Option<OracleTerms>,  // Oracle bets only
+    pub resolver_state: Option<Pubkey>,     // Resolver program's state account (Smart Contract only)
+    pub referee_fee_kind: u8,               // RefereeFeeKind enum value
+    pub referee_fee: u64,                   // Flat amount or basis points of the pot (0 = unpaid referee)
+    pub referee_panel: Option<RefereePanelTerms>, // Panel bets only
+    pub partial_fills: bool,                // Pool bet filled by many acceptors with fill_bet
+    pub parlay_legs: Option<Vec<ParlayLeg>>, // Parlay bets only
+}
+
+// Referee panel for Panel bets, e.g. "2 of 3 of these friends decide"
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct RefereePanel {
+    pub members: [Pubkey; MAX_PANEL_SIZE],  // Referee wallets (only the first `size` are used)
+    pub size: u8,                           // Number of referees on the panel (N)
+    pub threshold: u8,                      // Votes for the same outcome needed to settle (M)
+    pub votes: [u8; MAX_PANEL_SIZE],        // BetOutcome each referee voted for (0 = not voted yet)
+}
+
+impl RefereePanel {
+    pub const LEN: usize = 32 * MAX_PANEL_SIZE // members
+        + 1                      // size
+        + 1                      // threshold
+        + MAX_PANEL_SIZE;        // votes
+
+    // Position of a referee on the panel, None if they aren't on it
+    pub fn member_index(&self, key: &Pubkey) -> Option<usize> {
+        self.members[..self.size as usize].iter().position(|member| member == key)
+    }
+
+    // Outcome the panel has settled on, if any outcome has reached the threshold
+    pub fn decided_outcome(&self) -> Option<BetOutcome> {
+        let counts = self.vote_counts();
+        (1..counts.len())
+            .find(|&outcome| counts[outcome] >= self.threshold)
+            .and_then(|outcome| BetOutcome::try_from(outcome as u8).ok())
+    }
+
+    // True once no outcome can reach the threshold with the votes still outstanding
+    pub fn is_deadlocked(&self) -> bool {
+        let counts = self.vote_counts();
+        let outstanding = self.size - counts[0];
+        let leading = counts[1..].iter().copied().max().unwrap_or(0);
+        leading + outstanding < self.threshold
+    }
+
+    // Votes per BetOutcome, index 0 holds the number of referees who have voted
+    fn vote_counts(&self) -> [u8; 5] {
+        let mut counts = [0u8; 5];
+        for &vote in &self.votes[..self.size as usize] {
+            if vote != BetOutcome::Pending as u8 {
+                counts[0] += 1;
+                counts[vote as usize] += 1;
+            }
+        }
+        counts
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -40,12 +233,12 @@ pub enum BetAvailableTo {
 #[account]
 #[repr(C)]
 pub struct Bet {
-    pub referee: Pubkey,                    // Referee wallet (creator for Honor System, designated for Third Party)
+    pub referee: Pubkey,                    // Referee wallet (creator for Honor System, designated for Third Party, feed id for Oracle, resolver PDA for Smart Contract, unset for Parlay)
     pub creator: Pubkey,                    // Wallet of bet creator
     pub acceptor: Option<Pubkey>,            // Wallet of bet acceptor (None if not accepted)
     pub creator_username: [u8; 32],          // Username of bet creator (32 bytes)
     pub acceptor_username: [u8; 32],        // Username of bet acceptor (32 bytes, zeroed if not accepted)
-    pub bet_amount: u64,                    // Amount in lamports
+    pub bet_amount: u64,                    // Amount in lamports (or base units of mint for SPL bets)
     pub description: [u8; 128],             // Bet description (128 bytes - byte-aligned)
     pub referee_type: u8,                   // RefereeType enum value
     pub category: u8,                       // BetCategory enum value
@@ -53,7 +246,7 @@ pub struct Bet {
     pub odds_lose: u64,                     // Odds denominator (e.g., 1 in 3:1)
     pub expires_at: i64,                    // Unix timestamp when bet expires
     pub status: u8,                          // BetStatus enum value
-    pub winner: Option<Pubkey>,             // Winner wallet (None if not resolved)
+    pub winner: Option<Pubkey>,             // Winner wallet (None if not resolved, or resolved as Push/Void)
     pub created_at: i64,                    // Timestamp when bet was created
     pub accepted_at: Option<i64>,           // Timestamp when bet was accepted
     pub resolved_at: Option<i64>,           // Timestamp when bet was resolved
@@ -61,9 +254,54 @@ pub struct Bet {
     pub private_bet_recipient: Option<Pubkey>, // Recipient for private bets (None if not private)
     pub version: u8,                        // For future upgrades
     pub bump: u8,                           // PDA bump
-    pub _padding: [u8; 5],                  // padding for alignment (reduced from 6 to 5 to account for new fields)
+    pub outcome: u8,                        // Version 2 - BetOutcome enum value (0 = Pending until resolved).
+                                            // Taken from the version 1 padding, which create_bet always zeroed
+    pub _padding: [u8; 4],                  // padding for alignment (reduced from 5 to 4 for outcome)
+    // Version 2 - new fields only ever go after the existing ones (or into padding that was always
+    // written as zero), so migrate_bet only has to grow the account
+    pub resolve_by: i64,                    // Unix timestamp by which the referee must resolve the bet
+    pub creator_proposal: u8,               // BetOutcome proposed by creator (Mutual Consent, 0 = none yet)
+    pub acceptor_proposal: u8,              // BetOutcome proposed by acceptor (Mutual Consent, 0 = none yet)
+    pub dispute_fallback: u8,               // DisputeFallback enum value (0 = Refund, 1 = Arbiter)
+    pub arbiter: Option<Pubkey>,            // Arbiter who resolves disputed bets (None unless fallback is Arbiter)
+    pub challenge_window: i64,              // Seconds a referee's outcome can be disputed before payout (0 = pay immediately)
+    pub challenge_deadline: Option<i64>,    // When the challenge window on the pending outcome closes
+    pub disputed_by: Option<Pubkey>,        // Side that disputed the pending outcome (its original party, even if the position was transferred)
+    pub dispute_bond: u64,                  // Bond held in the treasury for the dispute (in lamports)
+    pub oracle: OracleTerms,                // Oracle terms (zeroed unless referee type is Oracle)
+    pub resolver_program: Option<Pubkey>,   // Program that settles the bet via CPI (Smart Contract only)
+    pub resolver_state: Option<Pubkey>,     // Resolver program's state account for this bet, e.g. a game (Smart Contract only)
+    pub mint: Option<Pubkey>,               // SPL mint the bet is made in (None for SOL bets, amounts are then in lamports)
+    pub protocol_fee_bps: u16,              // Protocol fee rate applied when the bet was settled
+    pub protocol_fee: u64,                  // Protocol fee taken from the pot when the bet was settled
+    pub referee_fee_kind: u8,               // RefereeFeeKind enum value (0 = Flat, 1 = Bps)
+    pub referee_fee: u64,                   // Referee fee, flat amount or basis points of the pot (0 = unpaid referee)
+    pub referee_fee_paid: u64,              // Referee fee paid out of the pot when the bet was settled
+    pub referee_accepted_at: Option<i64>,   // Timestamp when the third-party referee accepted the role
+    pub panel: RefereePanel,                // Referee panel and its votes (zeroed unless referee type is Panel)
+    pub terms_revision: u32,                // Number of times the terms have changed (update_bet or an accepted counter-offer)
+    pub counter_offer_count: u32,           // Counter-offers made on the bet (used in CounterOffer PDA seeds)
+    pub open_counter_offer_count: u32,      // Counter-offers still holding an escrowed stake (bet can't be deleted until 0)
+    pub settlement_proposer: Option<Pubkey>, // Party with an early settlement on the table (None if no proposal)
+    pub settlement_creator_amount: u64,     // Proposed creator share of the treasury, the acceptor gets the rest
+    pub creator_position_holder: Pubkey,    // Wallet paid for the creator's side (the creator until transfer_position is used)
+    pub acceptor_position_holder: Option<Pubkey>, // Wallet paid for the acceptor's side (None if not accepted)
+    pub partial_fills: bool,                // Pool bet - many acceptors each fill part of the creator's stake with fill_bet
+    pub filled_amount: u64,                 // Part of the creator's stake matched by fills (pool bets only)
+    pub filled_acceptor_amount: u64,        // Acceptor stakes put in by all fills (pool bets only)
+    pub fill_count: u32,                    // Fills made on the bet (used in Fill PDA seeds)
+    pub open_fill_count: u32,               // Fills not yet paid out by claim_fill (bet can't be deleted until 0)
+    pub linked_parlay_count: u32,           // Parlays using this bet as a leg (bet can't be deleted until they are)
+    pub creator_deposit: u64,               // Creator's stake currently held in the treasury
+    pub acceptor_deposit: u64,              // Acceptor's stake currently held in the treasury (every fill's for pool bets)
+    pub _v2_padding: [u8; 1],               // padding that rounds LEN up to a multiple of 8 (1064) - resize it whenever a field is added
 }
 
+// Catches a field being added without the padding above being resized. Written with % so it
+// still builds on the Solana toolchain's older rustc
+#[allow(clippy::manual_is_multiple_of)]
+const _: () = assert!(Bet::LEN % 8 == 0);
+
 impl Bet {
     pub const LEN: usize = 8     // discriminator
         + 32                     // referee
@@ -87,6 +325,90 @@ impl Bet {
         + 33                     // private_bet_recipient (Option<Pubkey>)
         + 1                      // version
         + 1                      // bump
-        + 5;                     // padding
-}
+        + 1                      // outcome
+        + 4                      // padding
+        + 8                      // resolve_by
+        + 1                      // creator_proposal
+        + 1                      // acceptor_proposal
+        + 1                      // dispute_fallback
+        + 33                     // arbiter (Option<Pubkey>)
+        + 8                      // challenge_window
+        + 9                      // challenge_deadline (Option<i64>)
+        + 33                     // disputed_by (Option<Pubkey>)
+        + 8                      // dispute_bond
+        + OracleTerms::LEN       // oracle
+        + 33                     // resolver_program (Option<Pubkey>)
+        + 33                     // resolver_state (Option<Pubkey>)
+        + 33                     // mint (Option<Pubkey>)
+        + 2                      // protocol_fee_bps
+        + 8                      // protocol_fee
+        + 1                      // referee_fee_kind
+        + 8                      // referee_fee
+        + 8                      // referee_fee_paid
+        + 9                      // referee_accepted_at (Option<i64>)
+        + RefereePanel::LEN      // panel
+        + 4                      // terms_revision
+        + 4                      // counter_offer_count
+        + 4                      // open_counter_offer_count
+        + 33                     // settlement_proposer (Option<Pubkey>)
+        + 8                      // settlement_creator_amount
+        + 32                     // creator_position_holder
+        + 33                     // acceptor_position_holder (Option<Pubkey>)
+        + 1                      // partial_fills
+        + 8                      // filled_amount
+        + 8                      // filled_acceptor_amount
+        + 4                      // fill_count
+        + 4                      // open_fill_count
+        + 4                      // linked_parlay_count
+        + 8                      // creator_deposit
+        + 8                      // acceptor_deposit
+        + 1;                     // version 2 padding
 
+    // Acceptor's bet amount: creator bet * (oddsWin / oddsLose)
+    // This ensures the payout ratios are correct
+    pub fn acceptor_bet_amount(&self) -> Result<u64> {
+        crate::math::mul_div(self.bet_amount, self.odds_win, self.odds_lose)
+    }
+
+    // Both stakes together, at the bet's terms
+    pub fn pot(&self) -> Result<u64> {
+        let pot = self.bet_amount
+            .checked_add(self.acceptor_bet_amount()?)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+        Ok(pot)
+    }
+
+    // Both stakes as recorded in the treasury
+    pub fn deposits(&self) -> Result<u64> {
+        let deposits = self.creator_deposit
+            .checked_add(self.acceptor_deposit)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+        Ok(deposits)
+    }
+
+    // Everything the treasury is recorded as holding for the bet. Payouts only ever come out of
+    // this - anything else in the treasury is excess, see BetTreasury::sweep_excess
+    pub fn recorded_balance(&self) -> Result<u64> {
+        let balance = self.deposits()?
+            .checked_add(self.dispute_bond)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+        Ok(balance)
+    }
+
+    // Fee owed to a paid third-party referee when the bet is resolved
+    pub fn referee_fee_amount(&self) -> Result<u64> {
+        if self.referee_fee_kind == RefereeFeeKind::Flat as u8 {
+            return Ok(self.referee_fee);
+        }
+        crate::math::bps_of(self.pot()?, self.referee_fee)
+    }
+
+    // Make sure the arbiter has time to rule before the bet becomes refundable
+    pub fn extend_for_arbitration(&mut self, now: i64) -> Result<()> {
+        let arbitration_deadline = now
+            .checked_add(crate::constants::ARBITRATION_PERIOD)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+        self.resolve_by = self.resolve_by.max(arbitration_deadline);
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[repr(C)]
+pub struct Config {
+    pub authority: Pubkey,                   // Admin allowed to update the config and withdraw fees
+    pub fee_recipient: Pubkey,               // Wallet withdrawn protocol fees are sent to
+    pub fee_bps: u16,                        // Protocol fee taken from the pot of won bets, in basis points
+    pub version: u8,                         // For future upgrades
+    pub bump: u8,                           // PDA bump
+    pub fee_vault_bump: u8,                  // Fee vault PDA bump
+    pub _padding: [u8; 3],                   // padding for alignment
+}
+
+impl Config {
+    pub const LEN: usize = 8     // discriminator
+        + 32                     // authority
+        + 32                     // fee_recipient
+        + 2                      // fee_bps
+        + 1                      // version
+        + 1                      // bump
+        + 1                      // fee_vault_bump
+        + 3;                     // padding
+}
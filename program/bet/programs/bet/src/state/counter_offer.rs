@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+// Offer to take an open bet on different terms. The offerer's stake is escrowed at
+// [b"counter-offer-escrow-", counter_offer] until the creator accepts it or it is closed.
+#[account]
+#[repr(C)]
+pub struct CounterOffer {
+    pub bet: Pubkey,                        // Bet the offer is for
+    pub offerer: Pubkey,                    // Wallet that would become the acceptor
+    pub bet_amount: u64,                    // Proposed creator stake (in lamports, or base units of the bet's mint)
+    pub odds_win: u64,                      // Proposed odds numerator
+    pub odds_lose: u64,                     // Proposed odds denominator
+    pub offerer_bet_amount: u64,            // Offerer's escrowed stake: bet_amount * (odds_win / odds_lose)
+    pub expires_at: i64,                    // Unix timestamp after which the creator can no longer accept the offer
+    pub created_at: i64,                    // Timestamp when the offer was made
+    pub version: u8,                        // For future upgrades
+    pub bump: u8,                           // PDA bump
+    pub escrow_bump: u8,                    // Escrow PDA bump
+    pub _padding: [u8; 5],                  // padding for alignment
+}
+
+impl CounterOffer {
+    pub const LEN: usize = 8     // discriminator
+        + 32                     // bet
+        + 32                     // offerer
+        + 8                      // bet_amount
+        + 8                      // odds_win
+        + 8                      // odds_lose
+        + 8                      // offerer_bet_amount
+        + 8                      // expires_at
+        + 8                      // created_at
+        + 1                      // version
+        + 1                      // bump
+        + 1                      // escrow_bump
+        + 5;                     // padding
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+// Program-owned account at [b"bet-treasury-", bet] or [b"market-treasury-", market] holding
+// the SOL stakes, created by create_bet / create_market with rent paid by the creator. Lamports
+// above its rent are the funds held (for SPL bets and markets it only signs for the token vault).
+// Closed by delete_bet / delete_market, which return the rent to the creator.
+#[account]
+#[repr(C)]
+pub struct Escrow {
+    pub parent: Pubkey,                     // Bet or market the escrow holds funds for
+    pub version: u8,                        // For future upgrades
+    pub bump: u8,                           // PDA bump
+    pub _padding: [u8; 6],                  // padding for alignment
+}
+
+impl Escrow {
+    pub const LEN: usize = 8     // discriminator
+        + 32                     // parent
+        + 1                      // version
+        + 1                      // bump
+        + 6;                     // padding
+}
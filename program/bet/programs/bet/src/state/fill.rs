@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+// One acceptor's slice of a pool bet, taken with fill_bet. Paid out by claim_fill once
+// the pool is resolved (or voided), which closes the account.
+#[account]
+#[repr(C)]
+pub struct Fill {
+    pub bet: Pubkey,                        // Pool bet the fill is for
+    pub acceptor: Pubkey,                   // Wallet that took the slice
+    pub creator_amount: u64,                // Part of the creator's stake this fill matches
+    pub acceptor_amount: u64,               // Acceptor's stake: creator_amount * (odds_win / odds_lose)
+    pub created_at: i64,                    // Timestamp when the fill was made
+    pub version: u8,                        // For future upgrades
+    pub bump: u8,                           // PDA bump
+    pub _padding: [u8; 6],                  // padding for alignment
+}
+
+impl Fill {
+    pub const LEN: usize = 8     // discriminator
+        + 32                     // bet
+        + 32                     // acceptor
+        + 8                      // creator_amount
+        + 8                      // acceptor_amount
+        + 8                      // created_at
+        + 1                      // version
+        + 1                      // bump
+        + 6;                     // padding
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_MARKET_OUTCOMES;
+
+// When and to whom a market is offered, as passed to create_market
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct MarketTerms {
+    pub expires_at: i64,                    // Unix timestamp when staking closes
+    pub resolve_by: i64,                    // Unix timestamp by which the referee must name the winning outcome
+    pub bet_available_to: u8,               // BetAvailableTo enum value
+    pub private_bet_recipient: Option<Pubkey>, // Only staker allowed on a Private market
+    pub resolver_state: Option<Pubkey>,     // Resolver program's state account (Smart Contract only)
+}
+
+// Parimutuel market on an event with up to MAX_MARKET_OUTCOMES named outcomes, e.g. "who wins
+// the office pool". Anyone can stake on an outcome; on resolution the losing pools are shared
+// among the winning pool's stakers in proportion to their stake. Stakes are held at
+// [b"market-treasury-", market] and paid out per stake with claim_market_stake.
+#[account]
+#[repr(C)]
+pub struct Market {
+    pub referee: Pubkey,                    // Referee wallet (creator for Honor System, designated for Third Party, resolver PDA for Smart Contract)
+    pub creator: Pubkey,                    // Wallet of market creator
+    pub creator_username: [u8; 32],         // Username of market creator (32 bytes)
+    pub description: [u8; 128],             // Market description (128 bytes - byte-aligned)
+    pub referee_type: u8,                   // RefereeType enum value (Honor System, Third Party or Smart Contract)
+    pub category: u8,                       // BetCategory enum value
+    pub bet_available_to: u8,               // BetAvailableTo enum value (0 = Public, 1 = FriendsOnly, 2 = Private)
+    pub private_bet_recipient: Option<Pubkey>, // Only wallet besides the creator that can stake on a private market
+    pub outcome_count: u8,                  // Number of named outcomes (only the first `outcome_count` are used)
+    pub outcome_labels: [[u8; 32]; MAX_MARKET_OUTCOMES], // Outcome names (32 bytes each)
+    pub outcome_pools: [u64; MAX_MARKET_OUTCOMES],       // Total staked on each outcome
+    pub total_pool: u64,                    // Total staked on all outcomes
+    pub expires_at: i64,                    // Unix timestamp when staking closes
+    pub resolve_by: i64,                    // Unix timestamp by which the referee must resolve the market
+    pub status: u8,                         // BetStatus enum value (Open, Resolved or Voided)
+    pub winning_outcome: Option<u8>,        // Index of the winning outcome (None until resolved, or if voided)
+    pub created_at: i64,                    // Timestamp when market was created
+    pub resolved_at: Option<i64>,           // Timestamp when market was resolved or voided
+    pub resolver_program: Option<Pubkey>,   // Program that resolves the market via CPI (Smart Contract only)
+    pub resolver_state: Option<Pubkey>,     // Resolver program's state account for this market (Smart Contract only)
+    pub mint: Option<Pubkey>,               // SPL mint the market is staked in (None for SOL, amounts are then in lamports)
+    pub protocol_fee_bps: u16,              // Protocol fee rate applied when the market was resolved
+    pub protocol_fee: u64,                  // Protocol fee taken from the pool when the market was resolved
+    pub stake_count: u32,                   // Stakes made on the market (used in MarketStake PDA seeds)
+    pub open_stake_count: u32,              // Stakes not yet claimed (market can't be deleted until 0)
+    pub version: u8,                        // For future upgrades
+    pub bump: u8,                           // PDA bump
+    pub _padding: [u8; 6],                  // padding for alignment
+}
+
+impl Market {
+    pub const LEN: usize = 8     // discriminator
+        + 32                     // referee
+        + 32                     // creator
+        + 32                     // creator_username
+        + 128                    // description
+        + 1                      // referee_type
+        + 1                      // category
+        + 1                      // bet_available_to
+        + 33                     // private_bet_recipient (Option<Pubkey>)
+        + 1                      // outcome_count
+        + 32 * MAX_MARKET_OUTCOMES // outcome_labels
+        + 8 * MAX_MARKET_OUTCOMES  // outcome_pools
+        + 8                      // total_pool
+        + 8                      // expires_at
+        + 8                      // resolve_by
+        + 1                      // status
+        + 2                      // winning_outcome (Option<u8>)
+        + 8                      // created_at
+        + 9                      // resolved_at (Option<i64>)
+        + 33                     // resolver_program (Option<Pubkey>)
+        + 33                     // resolver_state (Option<Pubkey>)
+        + 33                     // mint (Option<Pubkey>)
+        + 2                      // protocol_fee_bps
+        + 8                      // protocol_fee
+        + 4                      // stake_count
+        + 4                      // open_stake_count
+        + 1                      // version
+        + 1                      // bump
+        + 6;                     // padding
+}
+
+// One wallet's stake on one outcome of a market, taken with stake_market
+#[account]
+#[repr(C)]
+pub struct MarketStake {
+    pub market: Pubkey,                     // Market the stake is on
+    pub staker: Pubkey,                     // Wallet that staked
+    pub outcome: u8,                        // Index of the outcome staked on
+    pub amount: u64,                        // Amount staked (in lamports, or base units of the market's mint)
+    pub created_at: i64,                    // Timestamp when the stake was made
+    pub version: u8,                        // For future upgrades
+    pub bump: u8,                           // PDA bump
+    pub _padding: [u8; 5],                  // padding for alignment
+}
+
+impl MarketStake {
+    pub const LEN: usize = 8     // discriminator
+        + 32                     // market
+        + 32                     // staker
+        + 1                      // outcome
+        + 8                      // amount
+        + 8                      // created_at
+        + 1                      // version
+        + 1                      // bump
+        + 5;                     // padding
+}
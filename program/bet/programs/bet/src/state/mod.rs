@@ -1,8 +1,20 @@
 pub mod profile;
 pub mod bet;
 pub mod friend;
+pub mod config;
+pub mod counter_offer;
+pub mod fill;
+pub mod market;
+pub mod parlay;
+pub mod escrow;
 
 pub use profile::*;
 pub use bet::*;
 pub use friend::*;
+pub use config::*;
+pub use counter_offer::*;
+pub use fill::*;
+pub use market::*;
+pub use parlay::*;
+pub use escrow::*;
 
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_PARLAY_LEGS;
+use crate::state::bet::{Bet, BetStatus, PositionSide};
+
+// One leg of a parlay: an existing bet and the side that has to win it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ParlayLeg {
+    pub bet: Pubkey,                        // Leg bet
+    pub side: u8,                           // PositionSide enum value the parlay creator needs to win the leg
+}
+
+impl ParlayLeg {
+    pub const LEN: usize = 32    // bet
+        + 1;                     // side
+}
+
+// How a leg bet has turned out for the parlay
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LegResult {
+    Pending,         // Leg bet hasn't finished yet
+    Won,             // Leg went the parlay's way
+    Lost,            // Leg went the other way
+    NoResult,        // Leg ended without a winner (push, void, refund, early settlement, or never taken)
+}
+
+// Legs of a Parlay bet, at [b"parlay", bet]. Written by create_bet and read by settle_parlay.
+#[account]
+#[repr(C)]
+pub struct Parlay {
+    pub bet: Pubkey,                        // Parlay bet the legs belong to
+    pub legs: [ParlayLeg; MAX_PARLAY_LEGS], // Leg bets (only the first `leg_count` are used)
+    pub leg_count: u8,                      // Number of legs
+    pub version: u8,                        // For future upgrades
+    pub bump: u8,                           // PDA bump
+    pub _padding: [u8; 5],                  // padding for alignment
+}
+
+impl Parlay {
+    pub const LEN: usize = 8     // discriminator
+        + 32                     // bet
+        + ParlayLeg::LEN * MAX_PARLAY_LEGS // legs
+        + 1                      // leg_count
+        + 1                      // version
+        + 1                      // bump
+        + 5;                     // padding
+
+    pub fn legs(&self) -> &[ParlayLeg] {
+        &self.legs[..self.leg_count as usize]
+    }
+}
+
+// Result of a leg from its status and winner
+pub fn leg_result(leg_bet: &Bet, side: u8) -> LegResult {
+    if leg_bet.status == BetStatus::Resolved as u8 {
+        let party = if side == PositionSide::Creator as u8 {
+            Some(leg_bet.creator)
+        } else {
+            leg_bet.acceptor
+        };
+        return match leg_bet.winner {
+            None => LegResult::NoResult,
+            Some(winner) if Some(winner) == party => LegResult::Won,
+            Some(_) => LegResult::Lost,
+        };
+    }
+    if leg_bet.status == BetStatus::Voided as u8
+        || leg_bet.status == BetStatus::Settled as u8
+        || leg_bet.status == BetStatus::Cancelled as u8
+        || leg_bet.status == BetStatus::Expired as u8
+    {
+        return LegResult::NoResult;
+    }
+    LegResult::Pending
+}
+
+// Leg bets are passed as remaining accounts, so they are loaded and stored by hand
+pub fn read_leg(account: &AccountInfo) -> Result<Bet> {
+    require!(
+        account.owner == &crate::ID,
+        crate::error::BetError::InvalidParlay
+    );
+    let data = account.try_borrow_data()?;
+    Bet::try_deserialize(&mut &data[..])
+}
+
+pub fn write_leg(account: &AccountInfo, leg_bet: &Bet) -> Result<()> {
+    require!(
+        account.is_writable,
+        crate::error::BetError::InvalidParlay
+    );
+    let mut data = account.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    leg_bet.try_serialize(&mut writer)
+}
@@ -1,4 +1,16 @@
 use anchor_lang::prelude::*;
+use crate::constants::BPS_DENOMINATOR;
+use crate::state::bet::PositionSide;
+
+// How a finished bet ended for one of its parties
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BetResult {
+    Win = 0,
+    Loss = 1,
+    Push = 2,
+    Void = 3,
+    Settled = 4,     // Ended early by an agreed split
+}
 
 #[account]
 #[repr(C)]
@@ -14,12 +26,38 @@ pub struct Profile {
     pub total_accepted_bet_losses: u32,      // Total losses from bets accepted
     pub total_my_bet_profit: i64,            // Total profit/loss from bets created (can be negative)
     pub total_accepted_bet_profit: i64,      // Total profit/loss from bets accepted (can be negative)
-    pub total_my_bet_volume: u64,            // Total volume of bets created (in lamports, from resolved bets)
-    pub total_accepted_bet_volume: u64,      // Total volume of bets accepted (in lamports, from resolved bets)
+    pub total_my_bet_volume: u64,            // Total staked on bets created (in lamports, from finished bets)
+    pub total_accepted_bet_volume: u64,      // Total staked on bets accepted (in lamports, from finished bets)
     pub created_at: i64,                     // Timestamp when profile was created
     pub version: u8,                         // For future upgrades
     pub bump: u8,                           // PDA bump
-    pub _padding: [u8; 7],                   // padding for alignment
+    pub total_market_count: u32,             // Version 4 - total parimutuel markets created by this user (used in Market PDA seeds).
+                                             // Taken from the version 1 padding, which create_profile always zeroed
+    pub _padding: [u8; 3],                   // padding for alignment (reduced from 7 to 3 for total_market_count)
+    // Version 2 - new fields only ever go after the existing ones (or into padding that was always
+    // written as zero), so migrate_profile only has to grow the account
+    pub total_push_count: u32,               // Total bets resolved as a push (not a win or loss)
+    pub total_void_count: u32,               // Total bets resolved as void (not a win or loss)
+    // Version 3
+    pub total_settled_count: u32,            // Total bets ended early by an agreed split (not a win or loss)
+    // Version 5 - totals across both sides, recorded by Profile::record_result
+    pub total_staked: u64,                   // Total staked on finished bets, either side
+    pub total_returned: u64,                 // Total paid back on finished bets (stakes returned plus winnings)
+    pub realized_profit: i64,                // total_returned - total_staked
+    pub roi_bps: i64,                        // realized_profit / total_staked in basis points (can be negative)
+    pub biggest_win: u64,                    // Largest profit on a single win
+    pub current_streak: i32,                 // Wins in a row if positive, losses in a row if negative
+    pub longest_win_streak: u32,             // Most wins in a row
+    pub longest_loss_streak: u32,            // Most losses in a row
+    pub _stats_padding: [u8; 4],             // padding for alignment
+    // Version 6 - stakes in bets that haven't paid out yet. They stay with the original parties
+    // if a position is transferred, like the stats. Counter-offers only count once accepted
+    pub open_bet_count: u32,                 // Bets created and still waiting for an acceptor
+    pub active_bet_count: u32,               // Accepted bets (or pool fills) not yet paid out, either side
+    pub locked_as_creator: u64,              // Staked in open and accepted bets this user created
+    pub locked_as_acceptor: u64,             // Staked in bets (and pool fills) this user accepted
+    pub max_exposure: Option<u64>,           // Most the user allows to be locked at once, None = no limit
+    pub _exposure_padding: [u8; 7],          // padding for alignment
 }
 
 impl Profile {
@@ -40,6 +78,140 @@ impl Profile {
         + 8                      // created_at
         + 1                      // version
         + 1                      // bump
-        + 7;                     // padding
+        + 4                      // total_market_count
+        + 3                      // padding
+        + 4                      // total_push_count
+        + 4                      // total_void_count
+        + 4                      // total_settled_count
+        + 8                      // total_staked
+        + 8                      // total_returned
+        + 8                      // realized_profit
+        + 8                      // roi_bps
+        + 8                      // biggest_win
+        + 4                      // current_streak
+        + 4                      // longest_win_streak
+        + 4                      // longest_loss_streak
+        + 4                      // stats padding
+        + 4                      // open_bet_count
+        + 4                      // active_bet_count
+        + 8                      // locked_as_creator
+        + 8                      // locked_as_acceptor
+        + 1 + 8                  // max_exposure (Option<u64>)
+        + 7;                     // exposure padding
+
+    // Everything the user currently has locked in bets, as either side
+    pub fn exposure(&self) -> Result<u64> {
+        let exposure = self.locked_as_creator
+            .checked_add(self.locked_as_acceptor)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+        Ok(exposure)
+    }
+
+    // Lock a stake the user has just put into a bet, refusing it if that takes them past their max_exposure
+    pub fn lock_stake(&mut self, side: PositionSide, amount: u64) -> Result<()> {
+        let locked = match side {
+            PositionSide::Creator => &mut self.locked_as_creator,
+            PositionSide::Acceptor => &mut self.locked_as_acceptor,
+        };
+        *locked = locked
+            .checked_add(amount)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+
+        if let Some(max_exposure) = self.max_exposure {
+            require!(
+                self.exposure()? <= max_exposure,
+                crate::error::BetError::ExposureLimitExceeded
+            );
+        }
+        Ok(())
+    }
+
+    // Release a stake once the bet has paid it back out (or lost it). Saturates, since bets placed
+    // before the profile was migrated to version 6 were never locked
+    pub fn unlock_stake(&mut self, side: PositionSide, amount: u64) {
+        let locked = match side {
+            PositionSide::Creator => &mut self.locked_as_creator,
+            PositionSide::Acceptor => &mut self.locked_as_acceptor,
+        };
+        *locked = locked.saturating_sub(amount);
+    }
+
+    // Record one finished bet: the side the user was on, how it ended for them, what they staked
+    // and what came back to them. Every resolution path goes through here, so profit is always
+    // what came back less what was staked (fees included) and volume is always the user's own stake.
+    // Pushes, voids and settlements count towards the totals but leave streaks alone.
+    pub fn record_result(
+        &mut self,
+        side: PositionSide,
+        result: BetResult,
+        staked: u64,
+        returned: u64,
+    ) -> Result<()> {
+        let profit = (returned as i128)
+            .checked_sub(staked as i128)
+            .and_then(|profit| i64::try_from(profit).ok())
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+
+        let (wins, losses, side_profit, side_volume) = match side {
+            PositionSide::Creator => (
+                &mut self.total_my_bet_wins,
+                &mut self.total_my_bet_losses,
+                &mut self.total_my_bet_profit,
+                &mut self.total_my_bet_volume,
+            ),
+            PositionSide::Acceptor => (
+                &mut self.total_accepted_bet_wins,
+                &mut self.total_accepted_bet_losses,
+                &mut self.total_accepted_bet_profit,
+                &mut self.total_accepted_bet_volume,
+            ),
+        };
+        *side_profit = side_profit
+            .checked_add(profit)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+        *side_volume = side_volume
+            .checked_add(staked)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+
+        match result {
+            BetResult::Win => {
+                *wins += 1;
+                self.current_streak = self.current_streak.max(0)
+                    .checked_add(1)
+                    .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+                self.longest_win_streak = self.longest_win_streak.max(self.current_streak.unsigned_abs());
+                self.biggest_win = self.biggest_win.max(profit.max(0) as u64);
+            }
+            BetResult::Loss => {
+                *losses += 1;
+                self.current_streak = self.current_streak.min(0)
+                    .checked_sub(1)
+                    .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+                self.longest_loss_streak = self.longest_loss_streak.max(self.current_streak.unsigned_abs());
+            }
+            BetResult::Push => self.total_push_count += 1,
+            BetResult::Void => self.total_void_count += 1,
+            BetResult::Settled => self.total_settled_count += 1,
+        }
+
+        self.total_staked = self.total_staked
+            .checked_add(staked)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+        self.total_returned = self.total_returned
+            .checked_add(returned)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+        self.realized_profit = self.realized_profit
+            .checked_add(profit)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+        self.roi_bps = if self.total_staked == 0 {
+            0
+        } else {
+            // Saturates instead of failing the payout on absurd odds
+            let roi_bps = self.realized_profit as i128 * BPS_DENOMINATOR as i128 / self.total_staked as i128;
+            roi_bps.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+        };
+
+        Ok(())
+    }
 }
 
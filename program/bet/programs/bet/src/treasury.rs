@@ -0,0 +1,490 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::{
+    self, spl_token_2022, CloseAccount, HarvestWithheldTokensToMint, Mint, TokenAccount,
+    TokenInterface, TransferChecked,
+};
+use spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use crate::state::bet::Bet;
+use crate::state::counter_offer::CounterOffer;
+use crate::state::market::Market;
+
+// Transfer lamports out of a system-owned treasury PDA ([seed_prefix, seed_key], e.g. a counter-offer escrow).
+// Must use invoke_signed because treasury is a PDA and needs program signature
+pub fn transfer_from_treasury<'info>(
+    treasury: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    seed_prefix: &[u8],
+    seed_key: &Pubkey,
+    treasury_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    // Prepare seeds for PDA signing
+    let seeds = &[
+        seed_prefix,
+        seed_key.as_ref(),
+        &[treasury_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    anchor_lang::solana_program::program::invoke_signed(
+        &anchor_lang::solana_program::system_instruction::transfer(
+            treasury.key,
+            recipient.key,
+            amount,
+        ),
+        &[
+            treasury.clone(),
+            recipient.clone(),
+            system_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
+
+// Token side of an SPL bet - the vault is the treasury PDA's associated token account for bet.mint
+pub struct TokenVault<'info> {
+    pub mint: AccountInfo<'info>,
+    pub vault: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub decimals: u8,
+}
+
+// PDA seeds of a treasury: [prefix, key] with its bump
+pub struct TreasurySeeds {
+    pub prefix: &'static [u8],
+    pub key: Pubkey,
+    pub bump: u8,
+}
+
+// Funds held for a bet: lamports on the treasury PDA for SOL bets, or tokens in the
+// treasury's vault for SPL bets. Every instruction moves stakes through this so the
+// payout logic doesn't care which one the bet uses. Counter-offer escrows work the same
+// way, under their own PDA, and so do parimutuel markets.
+//
+// A bet's or market's treasury is a program-owned Escrow account holding its rent on top of
+// the stakes, paid out by moving lamports directly. Counter-offer escrows, and version 1 bets
+// brought up to date by migrate_bet, are plain system accounts paid out with a signed transfer.
+pub struct BetTreasury<'info> {
+    pub treasury: AccountInfo<'info>,
+    pub seeds: TreasurySeeds,
+    pub system_program: AccountInfo<'info>,
+    pub token: Option<TokenVault<'info>>,
+}
+
+impl<'info> BetTreasury<'info> {
+    // Token accounts are only looked at for SPL bets, where they must match bet.mint
+    pub fn new(
+        bet: &Account<'info, Bet>,
+        treasury: AccountInfo<'info>,
+        treasury_bump: u8,
+        system_program: AccountInfo<'info>,
+        mint: Option<&InterfaceAccount<'info, Mint>>,
+        vault: Option<&InterfaceAccount<'info, TokenAccount>>,
+        token_program: Option<&Interface<'info, TokenInterface>>,
+    ) -> Result<Self> {
+        let seeds = TreasurySeeds { prefix: b"bet-treasury-", key: bet.key(), bump: treasury_bump };
+        Self::with_seeds(bet.mint, seeds, treasury, system_program, mint, vault, token_program)
+    }
+
+    // Escrow PDA [b"counter-offer-escrow-", counter_offer] holding a counter-offer's stake, in the bet's currency.
+    // counter_offer.escrow_bump has to be set before this is called
+    pub fn for_counter_offer(
+        bet: &Account<'info, Bet>,
+        counter_offer: &Account<'info, CounterOffer>,
+        escrow: AccountInfo<'info>,
+        system_program: AccountInfo<'info>,
+        mint: Option<&InterfaceAccount<'info, Mint>>,
+        escrow_vault: Option<&InterfaceAccount<'info, TokenAccount>>,
+        token_program: Option<&Interface<'info, TokenInterface>>,
+    ) -> Result<Self> {
+        let seeds = TreasurySeeds {
+            prefix: b"counter-offer-escrow-",
+            key: counter_offer.key(),
+            bump: counter_offer.escrow_bump,
+        };
+        Self::with_seeds(bet.mint, seeds, escrow, system_program, mint, escrow_vault, token_program)
+    }
+
+    // Treasury PDA [b"market-treasury-", market] holding every stake on a parimutuel market
+    pub fn for_market(
+        market: &Account<'info, Market>,
+        treasury: AccountInfo<'info>,
+        treasury_bump: u8,
+        system_program: AccountInfo<'info>,
+        mint: Option<&InterfaceAccount<'info, Mint>>,
+        vault: Option<&InterfaceAccount<'info, TokenAccount>>,
+        token_program: Option<&Interface<'info, TokenInterface>>,
+    ) -> Result<Self> {
+        let seeds = TreasurySeeds { prefix: b"market-treasury-", key: market.key(), bump: treasury_bump };
+        Self::with_seeds(market.mint, seeds, treasury, system_program, mint, vault, token_program)
+    }
+
+    fn with_seeds(
+        currency_mint: Option<Pubkey>,
+        seeds: TreasurySeeds,
+        treasury: AccountInfo<'info>,
+        system_program: AccountInfo<'info>,
+        mint: Option<&InterfaceAccount<'info, Mint>>,
+        vault: Option<&InterfaceAccount<'info, TokenAccount>>,
+        token_program: Option<&Interface<'info, TokenInterface>>,
+    ) -> Result<Self> {
+        let token = match currency_mint {
+            None => None,
+            Some(bet_mint) => {
+                let mint = mint.ok_or(crate::error::BetError::InvalidMint)?;
+                require!(mint.key() == bet_mint, crate::error::BetError::InvalidMint);
+
+                let token_program = token_program.ok_or(crate::error::BetError::InvalidTokenAccount)?;
+                require!(
+                    mint.to_account_info().owner == token_program.key,
+                    crate::error::BetError::InvalidMint
+                );
+
+                let vault = vault.ok_or(crate::error::BetError::InvalidTokenAccount)?;
+                require!(
+                    vault.key() == get_associated_token_address_with_program_id(treasury.key, &bet_mint, token_program.key),
+                    crate::error::BetError::InvalidTokenAccount
+                );
+
+                Some(TokenVault {
+                    mint: mint.to_account_info(),
+                    vault: vault.to_account_info(),
+                    token_program: token_program.to_account_info(),
+                    decimals: mint.decimals,
+                })
+            }
+        };
+
+        Ok(Self {
+            treasury,
+            seeds,
+            system_program,
+            token,
+        })
+    }
+
+    pub fn is_token(&self) -> bool {
+        self.token.is_some()
+    }
+
+    // Everything currently held for the bet (read fresh, so it reflects earlier transfers in the instruction).
+    // An Escrow's rent isn't part of it
+    pub fn balance(&self) -> Result<u64> {
+        match &self.token {
+            None => {
+                let balance = self.treasury.lamports()
+                    .checked_sub(self.rent_reserve()?)
+                    .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+                Ok(balance)
+            }
+            Some(token) => token_account_amount(&token.vault),
+        }
+    }
+
+    fn is_escrow(&self) -> bool {
+        self.treasury.owner == &crate::ID
+    }
+
+    // Lamports an Escrow keeps for rent until close() hands them back
+    fn rent_reserve(&self) -> Result<u64> {
+        if !self.is_escrow() {
+            return Ok(0);
+        }
+        Ok(Rent::get()?.minimum_balance(self.treasury.data_len()))
+    }
+
+    // Move a stake into the treasury. SPL deposits are grossed up by the mint's transfer fee
+    // so the vault receives exactly `amount` and the payout math stays exact.
+    pub fn deposit(
+        &self,
+        payer: &AccountInfo<'info>,
+        payer_token_account: Option<AccountInfo<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let token = match &self.token {
+            None => {
+                anchor_lang::solana_program::program::invoke(
+                    &anchor_lang::solana_program::system_instruction::transfer(
+                        payer.key,
+                        self.treasury.key,
+                        amount,
+                    ),
+                    &[
+                        payer.clone(),
+                        self.treasury.clone(),
+                        self.system_program.clone(),
+                    ],
+                )?;
+                return Ok(());
+            }
+            Some(token) => token,
+        };
+
+        let payer_token_account = payer_token_account.ok_or(crate::error::BetError::InvalidTokenAccount)?;
+        let gross_amount = amount
+            .checked_add(transfer_fee_for_net_amount(&token.mint, amount)?)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+
+        let balance_before = self.balance()?;
+        token_interface::transfer_checked(
+            CpiContext::new(
+                token.token_program.clone(),
+                TransferChecked {
+                    from: payer_token_account,
+                    mint: token.mint.clone(),
+                    to: token.vault.clone(),
+                    authority: payer.clone(),
+                },
+            ),
+            gross_amount,
+            token.decimals,
+        )?;
+
+        // Catch anything else about the mint that changes what arrives in the vault
+        let received = self.balance()?
+            .checked_sub(balance_before)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+        require!(received == amount, crate::error::BetError::TokenTransferMismatch);
+
+        Ok(())
+    }
+
+    // Pay `amount` out of the treasury. SPL payouts go to a token account owned by the recipient,
+    // any transfer fee on the way out is withheld from what the recipient receives.
+    pub fn pay(
+        &self,
+        recipient: &AccountInfo<'info>,
+        recipient_token_account: Option<AccountInfo<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let token = match &self.token {
+            None if self.is_escrow() => {
+                // The program owns the escrow, so lamports are moved directly - never out of its rent
+                if amount == 0 {
+                    return Ok(());
+                }
+                require!(
+                    amount <= self.balance()?,
+                    crate::error::BetError::TreasuryUnderfunded
+                );
+                let recipient_balance = recipient.lamports()
+                    .checked_add(amount)
+                    .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+                **self.treasury.try_borrow_mut_lamports()? -= amount;
+                **recipient.try_borrow_mut_lamports()? = recipient_balance;
+                return Ok(());
+            }
+            None => {
+                return transfer_from_treasury(
+                    &self.treasury,
+                    recipient,
+                    &self.system_program,
+                    self.seeds.prefix,
+                    &self.seeds.key,
+                    self.seeds.bump,
+                    amount,
+                );
+            }
+            Some(token) => token,
+        };
+
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let recipient_token_account = recipient_token_account.ok_or(crate::error::BetError::InvalidTokenAccount)?;
+        {
+            let data = recipient_token_account.try_borrow_data()?;
+            let account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+            require!(
+                account.base.owner == recipient.key() && account.base.mint == token.mint.key(),
+                crate::error::BetError::InvalidTokenAccount
+            );
+        }
+
+        let seeds = &[
+            self.seeds.prefix,
+            self.seeds.key.as_ref(),
+            &[self.seeds.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                token.token_program.clone(),
+                TransferChecked {
+                    from: token.vault.clone(),
+                    mint: token.mint.clone(),
+                    to: recipient_token_account,
+                    authority: self.treasury.clone(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            token.decimals,
+        )
+    }
+
+    // Pay out whatever the treasury holds beyond what the bet records it holding, e.g. lamports
+    // sent to the treasury directly. Excess never changes a payout - it always goes to the
+    // creator's side, which paid for the treasury. Called wherever the creator's side is paid,
+    // so a SOL treasury is left holding nothing but its rent.
+    pub fn sweep_excess(
+        &self,
+        bet: &Bet,
+        creator: &AccountInfo<'info>,
+        creator_token_account: Option<AccountInfo<'info>>,
+    ) -> Result<()> {
+        let excess = self.balance()?.saturating_sub(bet.recorded_balance()?);
+        self.pay(creator, creator_token_account, excess)
+    }
+
+    // Transfer fee the mint charges on top of `net_amount` (0 for SOL and mints without one)
+    pub fn transfer_fee(&self, net_amount: u64) -> Result<u64> {
+        match &self.token {
+            None => Ok(0),
+            Some(token) => transfer_fee_for_net_amount(&token.mint, net_amount),
+        }
+    }
+
+    // Move exactly `amount` into another treasury of the same bet, e.g. a counter-offer's escrow
+    // into the bet treasury. SPL transfers are grossed up by the transfer fee like deposit.
+    pub fn move_to(&self, destination: &BetTreasury<'info>, amount: u64) -> Result<()> {
+        let (token, destination_token) = match (&self.token, &destination.token) {
+            (None, None) => return self.pay(&destination.treasury, None, amount),
+            (Some(token), Some(destination_token)) => (token, destination_token),
+            _ => return err!(crate::error::BetError::InvalidMint),
+        };
+
+        let gross_amount = amount
+            .checked_add(transfer_fee_for_net_amount(&token.mint, amount)?)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+
+        let seeds = &[
+            self.seeds.prefix,
+            self.seeds.key.as_ref(),
+            &[self.seeds.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let balance_before = destination.balance()?;
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                token.token_program.clone(),
+                TransferChecked {
+                    from: token.vault.clone(),
+                    mint: token.mint.clone(),
+                    to: destination_token.vault.clone(),
+                    authority: self.treasury.clone(),
+                },
+                signer_seeds,
+            ),
+            gross_amount,
+            token.decimals,
+        )?;
+
+        let received = destination.balance()?
+            .checked_sub(balance_before)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+        require!(received == amount, crate::error::BetError::TokenTransferMismatch);
+
+        Ok(())
+    }
+
+    // Close an Escrow once the bet or market is done with it, handing its rent (and anything still in it)
+    // to `rent_recipient`. System-owned treasuries are left empty by the payouts and need nothing.
+    pub fn close(&self, rent_recipient: &AccountInfo<'info>) -> Result<()> {
+        if !self.is_escrow() {
+            return Ok(());
+        }
+        let recipient_balance = rent_recipient.lamports()
+            .checked_add(self.treasury.lamports())
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?;
+        **self.treasury.try_borrow_mut_lamports()? = 0;
+        **rent_recipient.try_borrow_mut_lamports()? = recipient_balance;
+
+        // Same as Anchor's `close`: hand the account back to the system program with no data
+        self.treasury.assign(&anchor_lang::system_program::ID);
+        self.treasury.resize(0)?;
+        Ok(())
+    }
+
+    // Close the (empty) vault and return its rent. Transfer fees withheld in the vault
+    // are harvested to the mint first, Token-2022 won't close an account holding them.
+    pub fn close_vault(&self, rent_recipient: &AccountInfo<'info>) -> Result<()> {
+        let token = match &self.token {
+            None => return Ok(()),
+            Some(token) => token,
+        };
+
+        if has_transfer_fee(&token.mint)? {
+            token_interface::harvest_withheld_tokens_to_mint(
+                CpiContext::new(
+                    token.token_program.clone(),
+                    HarvestWithheldTokensToMint {
+                        token_program_id: token.token_program.clone(),
+                        mint: token.mint.clone(),
+                    },
+                ),
+                vec![token.vault.clone()],
+            )?;
+        }
+
+        let seeds = &[
+            self.seeds.prefix,
+            self.seeds.key.as_ref(),
+            &[self.seeds.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token_interface::close_account(CpiContext::new_with_signer(
+            token.token_program.clone(),
+            CloseAccount {
+                account: token.vault.clone(),
+                destination: rent_recipient.clone(),
+                authority: self.treasury.clone(),
+            },
+            signer_seeds,
+        ))
+    }
+}
+
+fn token_account_amount(token_account: &AccountInfo) -> Result<u64> {
+    let data = token_account.try_borrow_data()?;
+    let account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+    Ok(account.base.amount)
+}
+
+fn has_transfer_fee(mint: &AccountInfo) -> Result<bool> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(false);
+    }
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    Ok(mint.get_extension::<TransferFeeConfig>().is_ok())
+}
+
+// Fee the sender has to add on top of `net_amount` for the recipient to receive `net_amount`
+fn transfer_fee_for_net_amount(mint: &AccountInfo, net_amount: u64) -> Result<u64> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    let fee = match mint.get_extension::<TransferFeeConfig>() {
+        Ok(config) => config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, net_amount)
+            .ok_or(crate::error::BetError::ArithmeticOverflow)?,
+        Err(_) => 0,
+    };
+    Ok(fee)
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use bet::constants::RESOLVER_AUTHORITY_SEED;
+use bet::program::Bet as BetProgram;
+
+declare_id!("9i8keMKFar3ZZWuYjGBLCD9WoxVASwHkNe1frFFM6wG");
+
+// Smart Contract resolver for the bet program's tests, standing in for e.g. a chess program.
+// It settles a bet by calling resolve_bet, signing as the [RESOLVER_AUTHORITY_SEED, game] PDA
+// the bet names as its referee. Never deploy it - anyone can report any outcome.
+#[program]
+pub mod mock_resolver {
+    use super::*;
+
+    pub fn report_outcome(ctx: Context<ReportOutcome>, outcome: u8) -> Result<()> {
+        let game = ctx.accounts.game.key();
+        let signer_seeds: &[&[u8]] = &[
+            RESOLVER_AUTHORITY_SEED,
+            game.as_ref(),
+            &[ctx.bumps.resolver_authority],
+        ];
+
+        let cpi_accounts = bet::cpi::accounts::ResolveBet {
+            resolver: ctx.accounts.resolver_authority.to_account_info(),
+            referee: ctx.accounts.resolver_authority.to_account_info(),
+            creator: ctx.accounts.creator.to_account_info(),
+            acceptor: ctx.accounts.acceptor.to_account_info(),
+            creator_profile: ctx.accounts.creator_profile.to_account_info(),
+            acceptor_profile: ctx.accounts.acceptor_profile.to_account_info(),
+            bet: ctx.accounts.bet.to_account_info(),
+            treasury: ctx.accounts.treasury.to_account_info(),
+            config: ctx.accounts.config.to_account_info(),
+            fee_vault: ctx.accounts.fee_vault.to_account_info(),
+            creator_position_holder: None,
+            acceptor_position_holder: None,
+            system_program: ctx.accounts.system_program.to_account_info(),
+            mint: None,
+            vault: None,
+            creator_token_account: None,
+            acceptor_token_account: None,
+            fee_vault_token_account: None,
+            referee_token_account: None,
+            token_program: None,
+        };
+        let signer = &[signer_seeds];
+        bet::cpi::resolve_bet(
+            CpiContext::new_with_signer(ctx.accounts.bet_program.to_account_info(), cpi_accounts, signer),
+            outcome,
+        )
+    }
+}
+
+// SOL bets only - the bet program checks every account it is passed
+#[derive(Accounts)]
+pub struct ReportOutcome<'info> {
+    /// CHECK: Game the bet was made on, only used as the resolver PDA seed
+    pub game: UncheckedAccount<'info>,
+
+    /// CHECK: Resolver PDA the bet names as its referee, signs the resolve_bet CPI
+    #[account(
+        mut,
+        seeds = [RESOLVER_AUTHORITY_SEED, game.key().as_ref()],
+        bump
+    )]
+    pub resolver_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the bet program
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the bet program
+    #[account(mut)]
+    pub acceptor: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the bet program
+    #[account(mut)]
+    pub creator_profile: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the bet program
+    #[account(mut)]
+    pub acceptor_profile: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the bet program
+    #[account(mut)]
+    pub bet: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the bet program
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the bet program
+    pub config: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the bet program
+    #[account(mut)]
+    pub fee_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub bet_program: Program<'info, BetProgram>,
+}